@@ -2,7 +2,7 @@ extern crate rspec;
 
 use std::collections::BTreeSet;
 
-pub fn main() {
+pub fn main() -> rspec::RunSummary {
     #[derive(Clone, Debug)]
     struct Environment {
         set: BTreeSet<usize>,
@@ -49,8 +49,8 @@ pub fn main() {
             ctx.then("it is still empty", |env| assert!(env.set.is_empty()));
         });
 
-        ctx.then("panic!(…) fails", |_env| {
+        ctx.then("panic!(…) fails", |_env| -> () {
             panic!("Some reason for failure.")
         });
-    }));
+    }))
 }
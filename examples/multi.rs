@@ -6,7 +6,7 @@ use std::sync::Arc;
 // An example of a single runner running multiple semantically equivalent,
 // yet syntactically different test suites in succession:
 
-pub fn main() {
+pub fn main() -> rspec::RunSummary {
     let logger = Arc::new(rspec::Logger::new(io::stdout()));
     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
     let runner = rspec::Runner::new(configuration, vec![logger]);
@@ -37,4 +37,6 @@ pub fn main() {
             });
         });
     }));
+
+    runner.finish()
 }
@@ -0,0 +1,30 @@
+#[macro_use]
+extern crate rspec;
+
+// An example of suites of different environment types, defined in separate modules,
+// getting registered and then discovered and run by a generated `main`:
+
+mod numbers {
+    pub fn suite() -> rspec::block::Suite<i32> {
+        rspec::describe("a value of ten", 10, |ctx| {
+            ctx.it("is even", |num| num % 2 == 0);
+        })
+    }
+
+    register!(suite);
+}
+
+mod strings {
+    pub fn suite() -> rspec::block::Suite<String> {
+        rspec::describe("a greeting", "hello".to_owned(), |ctx| {
+            ctx.it("is lowercase", |greeting| {
+                greeting.to_lowercase() == *greeting
+            });
+        })
+    }
+
+    register!(suite);
+}
+
+// Pass `--suite <name>` (or any of libtest's arguments) to select what gets run:
+main!();
@@ -1,6 +1,6 @@
 extern crate rspec;
 
-pub fn main() {
+pub fn main() -> rspec::RunSummary {
     // The easiest way to open a suite is by calling the `rspec::run(…)` function,
     // passing it the result of one of these functions:
     //
@@ -45,5 +45,5 @@ pub fn main() {
                 assert_eq!(*value, 42);
             });
         });
-    }));
+    }))
 }
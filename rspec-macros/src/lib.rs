@@ -0,0 +1,175 @@
+//! Procedural macros of [rspec](https://crates.io/crates/rspec).
+//!
+//! Use them through rspec's `rspec!` macro, which passes them its crate's path.
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+
+use std::iter::Peekable;
+
+use proc_macro2::token_stream::IntoIter;
+use proc_macro2::{Delimiter, Ident, Span, TokenStream, TokenTree};
+
+type Tokens = Peekable<IntoIter>;
+
+type Error = (Span, &'static str);
+
+/// Expands `$crate; <suite>` into calls of rspec's suite function and `Context`'s methods.
+///
+/// Each call is spanned at the label or method it was expanded from,
+/// so that the location each suite, context and example captures
+/// is that of its own declaration, rather than that of the macro's invocation.
+#[proc_macro]
+pub fn rspec(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let mut tokens = TokenStream::from(input).into_iter().peekable();
+    let expanded = match tokens.next() {
+        Some(krate) => {
+            let _semicolon = tokens.next();
+            suite(&krate, &mut tokens)
+        }
+        None => Err((Span::call_site(), "expected the crate's path")),
+    };
+    let expanded =
+        expanded.unwrap_or_else(|(span, message)| quote_spanned!(span=> compile_error!(#message)));
+    expanded.into()
+}
+
+fn suite(krate: &TokenTree, tokens: &mut Tokens) -> Result<TokenStream, Error> {
+    let label = ident(tokens, "expected a suite's label (e.g. `describe`)")?;
+    let span = label.span();
+    let name = literal(tokens, span, "expected the suite's name")?;
+    let environment = match tokens.peek() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+            let environment = group.stream();
+            tokens.next();
+            environment
+        }
+        _ => quote!(()),
+    };
+    let body = braces(tokens, span, "expected the suite's blocks in braces")?;
+    if let Some(token) = tokens.next() {
+        return Err((token.span(), "unexpected token after the suite"));
+    }
+    let ctx = context(span);
+    let blocks = blocks(body)?;
+    // Importing the label lets it be called by a path spanned at the label,
+    // which `$crate::#label` isn't.
+    Ok(quote_spanned!(span=> {
+        use #krate::#label;
+        #label(#name, #environment, |#ctx| { #blocks })
+    }))
+}
+
+fn blocks(body: TokenStream) -> Result<TokenStream, Error> {
+    let mut tokens = body.into_iter().peekable();
+    let mut blocks = TokenStream::new();
+    while tokens.peek().is_some() {
+        blocks.extend(block(&mut tokens)?);
+    }
+    Ok(blocks)
+}
+
+fn block(tokens: &mut Tokens) -> Result<TokenStream, Error> {
+    let method = ident(
+        tokens,
+        "expected a block (e.g. `it`, `context`, `before_each`)",
+    )?;
+    let span = method.span();
+    let ctx = context(span);
+    let block = match tokens.peek() {
+        // Contexts (e.g. `context`, `specify`, `when`) and examples (e.g. `it`, `example`, `then`):
+        Some(TokenTree::Literal(_)) => {
+            let name = literal(tokens, span, "expected the block's name")?;
+            if is_braces(tokens.peek()) {
+                let body = blocks(braces(tokens, span, "expected the context's blocks")?)?;
+                quote_spanned!(span=> #ctx.#method(#name, |#ctx| { #body });)
+            } else {
+                let closure = closure(tokens, span)?;
+                quote_spanned!(span=> #ctx.#method(#name, #closure);)
+            }
+        }
+        // Scopes:
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+            let body = blocks(braces(tokens, span, "expected the scope's blocks")?)?;
+            quote_spanned!(span=> #ctx.#method(|#ctx| { #body });)
+        }
+        // Hooks (e.g. `before_each`, `after_all`, `around_each`):
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '|' => {
+            let closure = closure(tokens, span)?;
+            quote_spanned!(span=> #ctx.#method(#closure);)
+        }
+        _ => return Err((span, "expected a name, a closure or blocks in braces")),
+    };
+    Ok(block)
+}
+
+/// Parses `|parameters| { body }` or `|parameters| body;` into a `move` closure.
+fn closure(tokens: &mut Tokens, span: Span) -> Result<TokenStream, Error> {
+    match tokens.next() {
+        Some(TokenTree::Punct(ref punct)) if punct.as_char() == '|' => {}
+        _ => return Err((span, "expected a closure")),
+    }
+    let mut parameters = TokenStream::new();
+    loop {
+        match tokens.next() {
+            Some(TokenTree::Punct(ref punct)) if punct.as_char() == '|' => break,
+            Some(token) => parameters.extend(Some(token)),
+            None => return Err((span, "expected the closure's closing `|`")),
+        }
+    }
+    let body = if is_braces(tokens.peek()) {
+        tokens.next().into_iter().collect()
+    } else {
+        let mut body = TokenStream::new();
+        loop {
+            match tokens.next() {
+                Some(TokenTree::Punct(ref punct)) if punct.as_char() == ';' => break,
+                Some(token) => body.extend(Some(token)),
+                None => return Err((span, "expected a block, or an expression terminated by `;`")),
+            }
+        }
+        body
+    };
+    Ok(quote!(move |#parameters| #body))
+}
+
+/// Returns the identifier of the context being declared on,
+/// hygienic but located at `span`.
+fn context(span: Span) -> Ident {
+    Ident::new("ctx", Span::mixed_site().located_at(span))
+}
+
+fn ident(tokens: &mut Tokens, message: &'static str) -> Result<Ident, Error> {
+    match tokens.next() {
+        Some(TokenTree::Ident(ident)) => Ok(ident),
+        Some(token) => Err((token.span(), message)),
+        None => Err((Span::call_site(), message)),
+    }
+}
+
+fn literal(tokens: &mut Tokens, span: Span, message: &'static str) -> Result<TokenTree, Error> {
+    match tokens.next() {
+        Some(token @ TokenTree::Literal(_)) => Ok(token),
+        Some(token) => Err((token.span(), message)),
+        None => Err((span, message)),
+    }
+}
+
+fn braces(tokens: &mut Tokens, span: Span, message: &'static str) -> Result<TokenStream, Error> {
+    match tokens.next() {
+        Some(TokenTree::Group(ref group)) if group.delimiter() == Delimiter::Brace => {
+            Ok(group.stream())
+        }
+        Some(token) => Err((token.span(), message)),
+        None => Err((span, message)),
+    }
+}
+
+fn is_braces(token: Option<&TokenTree>) -> bool {
+    match token {
+        Some(TokenTree::Group(group)) => group.delimiter() == Delimiter::Brace,
+        _ => false,
+    }
+}
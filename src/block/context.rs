@@ -8,18 +8,31 @@
 //! Running these tests and doing asserts is not the job of the Context, but the Runner.
 //!
 
-use block::{Block, Example};
+use std::panic::Location;
+
+use block::{Block, Example, Mark};
 use header::{ContextHeader, ContextLabel, ExampleHeader, ExampleLabel};
 use report::ExampleResult;
+use runner::panics;
+
+use time::Duration;
+
+pub(crate) type Hook<T> = Box<dyn Fn(&mut T) -> ExampleResult>;
+pub(crate) type AroundHook<T> = Box<dyn Fn(&mut T, &mut dyn FnMut(&mut T)) -> ExampleResult>;
 
 /// Test contexts are a convenient tool for adding structure and code sharing to a test suite.
 pub struct Context<T> {
     pub(crate) header: Option<ContextHeader>,
     pub(crate) blocks: Vec<Block<T>>,
-    pub(crate) before_all: Vec<Box<dyn Fn(&mut T)>>,
-    pub(crate) before_each: Vec<Box<dyn Fn(&mut T)>>,
-    pub(crate) after_all: Vec<Box<dyn Fn(&mut T)>>,
-    pub(crate) after_each: Vec<Box<dyn Fn(&mut T)>>,
+    pub(crate) mark: Mark,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) retries: Option<u32>,
+    pub(crate) before_all: Vec<Hook<T>>,
+    pub(crate) before_each: Vec<Hook<T>>,
+    pub(crate) after_all: Vec<Hook<T>>,
+    pub(crate) after_each: Vec<Hook<T>>,
+    pub(crate) around_all: Vec<AroundHook<T>>,
+    pub(crate) around_each: Vec<AroundHook<T>>,
 }
 
 impl<T> Context<T> {
@@ -27,10 +40,15 @@ impl<T> Context<T> {
         Context {
             header,
             blocks: vec![],
+            mark: Mark::Default,
+            timeout: None,
+            retries: None,
             before_all: vec![],
             before_each: vec![],
             after_all: vec![],
             after_each: vec![],
+            around_all: vec![],
+            around_each: vec![],
         }
     }
 
@@ -45,6 +63,11 @@ impl<T> Context<T> {
     pub fn is_empty(&self) -> bool {
         self.blocks.is_empty()
     }
+
+    /// Whether the context is focused or contains focused blocks.
+    pub fn is_focused(&self) -> bool {
+        self.mark == Mark::Focused || self.blocks.iter().any(|b| b.is_focused())
+    }
 }
 
 // Both `Send` and `Sync` are necessary for parallel threaded execution.
@@ -53,10 +76,7 @@ unsafe impl<T> Send for Context<T> where T: Send {}
 // Both `Send` and `Sync` are necessary for parallel threaded execution.
 unsafe impl<T> Sync for Context<T> where T: Sync {}
 
-impl<T> Context<T>
-where
-    T: Clone,
-{
+impl<T> Context<T> {
     /// Open and name a new context within the current context.
     ///
     /// Note that the order of execution **IS NOT** guaranteed to match the declaration order.
@@ -95,16 +115,14 @@ where
     ///
     /// - [`specify`](struct.Context.html#method.specify).
     /// - [`when`](struct.Context.html#method.when).
+    #[track_caller]
     pub fn context<F>(&mut self, name: &'static str, body: F)
     where
         F: FnOnce(&mut Context<T>),
         T: ::std::fmt::Debug,
     {
-        let header = ContextHeader {
-            label: ContextLabel::Context,
-            name,
-        };
-        self.context_internal(Some(header), body)
+        let header = ContextHeader::new(ContextLabel::Context, name);
+        self.context_internal(Some(header), Mark::Default, body)
     }
 
     /// Alias for [`context`](struct.Context.html#method.context), see for more info.
@@ -112,16 +130,14 @@ where
     /// Available further aliases:
     ///
     /// - [`when`](struct.Context.html#method.when).
+    #[track_caller]
     pub fn specify<F>(&mut self, name: &'static str, body: F)
     where
         F: FnOnce(&mut Context<T>),
         T: ::std::fmt::Debug,
     {
-        let header = ContextHeader {
-            label: ContextLabel::Specify,
-            name,
-        };
-        self.context_internal(Some(header), body)
+        let header = ContextHeader::new(ContextLabel::Specify, name);
+        self.context_internal(Some(header), Mark::Default, body)
     }
 
     /// Alias for [`context`](struct.Context.html#method.context), see for more info.
@@ -129,16 +145,177 @@ where
     /// Available further aliases:
     ///
     /// - [`specify`](struct.Context.html#method.specify).
+    #[track_caller]
     pub fn when<F>(&mut self, name: &'static str, body: F)
     where
         F: FnOnce(&mut Context<T>),
         T: ::std::fmt::Debug,
     {
-        let header = ContextHeader {
-            label: ContextLabel::When,
-            name,
-        };
-        self.context_internal(Some(header), body)
+        let header = ContextHeader::new(ContextLabel::When, name);
+        self.context_internal(Some(header), Mark::Default, body)
+    }
+
+    /// Open and name a new focused context within the current context.
+    ///
+    /// As soon as a suite contains any focused contexts or examples, the runner
+    /// only executes those (including all of their children) and reports all
+    /// others as filtered out. This comes in handy when debugging a single scenario,
+    /// but should never get merged, which is why loggers warn about focused runs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// runner.run(&rspec::suite("a test suite", (), |ctx| {
+    ///     ctx.fcontext("a focused context", |ctx| {
+    ///         ctx.example("an example that gets executed", |_env| {
+    ///             // …
+    ///         });
+    ///     });
+    ///
+    ///     ctx.example("an example that gets filtered out", |_env| {
+    ///         // …
+    ///     });
+    /// }));
+    /// # }
+    /// ```
+    ///
+    /// Available aliases:
+    ///
+    /// - [`fspecify`](struct.Context.html#method.fspecify).
+    /// - [`fwhen`](struct.Context.html#method.fwhen).
+    #[track_caller]
+    pub fn fcontext<F>(&mut self, name: &'static str, body: F)
+    where
+        F: FnOnce(&mut Context<T>),
+        T: ::std::fmt::Debug,
+    {
+        let header = ContextHeader::new(ContextLabel::Context, name);
+        self.context_internal(Some(header), Mark::Focused, body)
+    }
+
+    /// Alias for [`fcontext`](struct.Context.html#method.fcontext), see for more info.
+    ///
+    /// Available further aliases:
+    ///
+    /// - [`fwhen`](struct.Context.html#method.fwhen).
+    #[track_caller]
+    pub fn fspecify<F>(&mut self, name: &'static str, body: F)
+    where
+        F: FnOnce(&mut Context<T>),
+        T: ::std::fmt::Debug,
+    {
+        let header = ContextHeader::new(ContextLabel::Specify, name);
+        self.context_internal(Some(header), Mark::Focused, body)
+    }
+
+    /// Alias for [`fcontext`](struct.Context.html#method.fcontext), see for more info.
+    ///
+    /// Available further aliases:
+    ///
+    /// - [`fspecify`](struct.Context.html#method.fspecify).
+    #[track_caller]
+    pub fn fwhen<F>(&mut self, name: &'static str, body: F)
+    where
+        F: FnOnce(&mut Context<T>),
+        T: ::std::fmt::Debug,
+    {
+        let header = ContextHeader::new(ContextLabel::When, name);
+        self.context_internal(Some(header), Mark::Focused, body)
+    }
+
+    /// Open and name a new pending context within the current context.
+    ///
+    /// Neither the context's `before`/`after` blocks nor any of its examples get executed,
+    /// instead its examples get reported as pending with the given reason. This comes in handy
+    /// for temporarily disabling a set of examples without having to comment them out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// runner.run(&rspec::suite("a test suite", (), |ctx| {
+    ///     ctx.xcontext("a pending context", "waiting for upstream fix", |ctx| {
+    ///         ctx.example("an example that gets reported as pending", |_env| {
+    ///             // …
+    ///         });
+    ///     });
+    /// }));
+    /// # }
+    /// ```
+    ///
+    /// Corresponding console output:
+    ///
+    /// ```text
+    /// tests:
+    /// Suite "a test suite":
+    ///     Context "a pending context":
+    ///         Example "an example that gets reported as pending" ... pending (waiting for upstream fix)
+    /// ```
+    ///
+    /// Available aliases:
+    ///
+    /// - [`xspecify`](struct.Context.html#method.xspecify).
+    /// - [`xwhen`](struct.Context.html#method.xwhen).
+    #[track_caller]
+    pub fn xcontext<F, S>(&mut self, name: &'static str, reason: S, body: F)
+    where
+        F: FnOnce(&mut Context<T>),
+        S: Into<String>,
+        T: ::std::fmt::Debug,
+    {
+        let header = ContextHeader::new(ContextLabel::Context, name);
+        self.context_internal(Some(header), Mark::Pending(Some(reason.into())), body)
+    }
+
+    /// Alias for [`xcontext`](struct.Context.html#method.xcontext), see for more info.
+    ///
+    /// Available further aliases:
+    ///
+    /// - [`xwhen`](struct.Context.html#method.xwhen).
+    #[track_caller]
+    pub fn xspecify<F, S>(&mut self, name: &'static str, reason: S, body: F)
+    where
+        F: FnOnce(&mut Context<T>),
+        S: Into<String>,
+        T: ::std::fmt::Debug,
+    {
+        let header = ContextHeader::new(ContextLabel::Specify, name);
+        self.context_internal(Some(header), Mark::Pending(Some(reason.into())), body)
+    }
+
+    /// Alias for [`xcontext`](struct.Context.html#method.xcontext), see for more info.
+    ///
+    /// Available further aliases:
+    ///
+    /// - [`xspecify`](struct.Context.html#method.xspecify).
+    #[track_caller]
+    pub fn xwhen<F, S>(&mut self, name: &'static str, reason: S, body: F)
+    where
+        F: FnOnce(&mut Context<T>),
+        S: Into<String>,
+        T: ::std::fmt::Debug,
+    {
+        let header = ContextHeader::new(ContextLabel::When, name);
+        self.context_internal(Some(header), Mark::Pending(Some(reason.into())), body)
     }
 
     /// Open a new name-less context within the current context which won't show up in the logs.
@@ -189,15 +366,22 @@ where
         F: FnOnce(&mut Context<T>),
         T: ::std::fmt::Debug,
     {
-        self.context_internal(None, body)
+        self.context_internal(None, Mark::Default, body)
     }
 
-    fn context_internal<F>(&mut self, header: Option<ContextHeader>, body: F)
+    #[track_caller]
+    fn context_internal<F>(&mut self, header: Option<ContextHeader>, mark: Mark, body: F)
     where
         F: FnOnce(&mut Context<T>),
         T: ::std::fmt::Debug,
     {
+        let location = Location::caller();
+        let header = header.map(|mut header| {
+            header.location = Some(location);
+            header
+        });
         let mut child = Context::new(header);
+        child.mark = mark;
         body(&mut child);
         self.blocks.push(Block::Context(child))
     }
@@ -240,66 +424,442 @@ where
     ///
     /// - [`it`](struct.Context.html#method.it).
     /// - [`then`](struct.Context.html#method.then).
+    #[track_caller]
     pub fn example<F, U>(&mut self, name: &'static str, body: F)
     where
-        F: 'static + Fn(&T) -> U,
+        F: 'static + Send + Sync + Fn(&T) -> U,
         U: Into<ExampleResult>,
     {
         let header = ExampleHeader::new(ExampleLabel::Example, name);
-        self.example_internal(header, body)
+        self.example_internal(header, Mark::Default, move |environment| body(environment))
+    }
+
+    /// Alias for [`example`](struct.Context.html#method.example), see for more info.
+    ///
+    /// Available further aliases:
+    ///
+    /// - [`it`](struct.Context.html#method.it).
+    #[track_caller]
+    pub fn it<F, U>(&mut self, name: &'static str, body: F)
+    where
+        F: 'static + Send + Sync + Fn(&T) -> U,
+        U: Into<ExampleResult>,
+    {
+        let header = ExampleHeader::new(ExampleLabel::It, name);
+        self.example_internal(header, Mark::Default, move |environment| body(environment))
     }
 
     /// Alias for [`example`](struct.Context.html#method.example), see for more info.
     ///
-    /// Available further aliases:
+    /// Available further aliases:
+    ///
+    /// - [`it`](struct.Context.html#method.it).
+    #[track_caller]
+    pub fn then<F, U>(&mut self, name: &'static str, body: F)
+    where
+        F: 'static + Send + Sync + Fn(&T) -> U,
+        U: Into<ExampleResult>,
+    {
+        let header = ExampleHeader::new(ExampleLabel::Then, name);
+        self.example_internal(header, Mark::Default, move |environment| body(environment))
+    }
+
+    /// Open and name a new focused example within the current context.
+    ///
+    /// As soon as a suite contains any focused contexts or examples, the runner
+    /// only executes those and reports all others as filtered out
+    /// (see [`fcontext`](struct.Context.html#method.fcontext) for more info).
+    ///
+    /// Available aliases:
+    ///
+    /// - [`fit`](struct.Context.html#method.fit).
+    /// - [`fthen`](struct.Context.html#method.fthen).
+    #[track_caller]
+    pub fn fexample<F, U>(&mut self, name: &'static str, body: F)
+    where
+        F: 'static + Send + Sync + Fn(&T) -> U,
+        U: Into<ExampleResult>,
+    {
+        let header = ExampleHeader::new(ExampleLabel::Example, name);
+        self.example_internal(header, Mark::Focused, move |environment| body(environment))
+    }
+
+    /// Alias for [`fexample`](struct.Context.html#method.fexample), see for more info.
+    ///
+    /// Available further aliases:
+    ///
+    /// - [`fthen`](struct.Context.html#method.fthen).
+    #[track_caller]
+    pub fn fit<F, U>(&mut self, name: &'static str, body: F)
+    where
+        F: 'static + Send + Sync + Fn(&T) -> U,
+        U: Into<ExampleResult>,
+    {
+        let header = ExampleHeader::new(ExampleLabel::It, name);
+        self.example_internal(header, Mark::Focused, move |environment| body(environment))
+    }
+
+    /// Alias for [`fexample`](struct.Context.html#method.fexample), see for more info.
+    ///
+    /// Available further aliases:
+    ///
+    /// - [`fit`](struct.Context.html#method.fit).
+    #[track_caller]
+    pub fn fthen<F, U>(&mut self, name: &'static str, body: F)
+    where
+        F: 'static + Send + Sync + Fn(&T) -> U,
+        U: Into<ExampleResult>,
+    {
+        let header = ExampleHeader::new(ExampleLabel::Then, name);
+        self.example_internal(header, Mark::Focused, move |environment| body(environment))
+    }
+
+    /// Open and name a new pending example within the current context.
+    ///
+    /// The example's body doesn't get executed, instead the example gets reported as pending
+    /// with the given reason.
+    ///
+    /// Available aliases:
+    ///
+    /// - [`xit`](struct.Context.html#method.xit).
+    /// - [`xthen`](struct.Context.html#method.xthen).
+    #[track_caller]
+    pub fn xexample<F, U, S>(&mut self, name: &'static str, reason: S, body: F)
+    where
+        F: 'static + Send + Sync + Fn(&T) -> U,
+        U: Into<ExampleResult>,
+        S: Into<String>,
+    {
+        let header = ExampleHeader::new(ExampleLabel::Example, name);
+        self.example_internal(
+            header,
+            Mark::Pending(Some(reason.into())),
+            move |environment| body(environment),
+        )
+    }
+
+    /// Alias for [`xexample`](struct.Context.html#method.xexample), see for more info.
+    ///
+    /// Available further aliases:
+    ///
+    /// - [`xthen`](struct.Context.html#method.xthen).
+    #[track_caller]
+    pub fn xit<F, U, S>(&mut self, name: &'static str, reason: S, body: F)
+    where
+        F: 'static + Send + Sync + Fn(&T) -> U,
+        U: Into<ExampleResult>,
+        S: Into<String>,
+    {
+        let header = ExampleHeader::new(ExampleLabel::It, name);
+        self.example_internal(
+            header,
+            Mark::Pending(Some(reason.into())),
+            move |environment| body(environment),
+        )
+    }
+
+    /// Alias for [`xexample`](struct.Context.html#method.xexample), see for more info.
+    ///
+    /// Available further aliases:
+    ///
+    /// - [`xit`](struct.Context.html#method.xit).
+    #[track_caller]
+    pub fn xthen<F, U, S>(&mut self, name: &'static str, reason: S, body: F)
+    where
+        F: 'static + Send + Sync + Fn(&T) -> U,
+        U: Into<ExampleResult>,
+        S: Into<String>,
+    {
+        let header = ExampleHeader::new(ExampleLabel::Then, name);
+        self.example_internal(
+            header,
+            Mark::Pending(Some(reason.into())),
+            move |environment| body(environment),
+        )
+    }
+
+    /// Open and name a new example within the current context,
+    /// whose body gets mutable access to the environment.
+    ///
+    /// Each example gets executed with its own copy of the environment
+    /// (set up by the context's hooks), so that mutating it doesn't affect any other blocks.
+    /// This allows for exercising the environment's `&mut self` methods
+    /// without resorting to interior mutability.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// runner.run(&rspec::suite("a vector", vec![1, 2, 3], |ctx| {
+    ///     ctx.example_mut("can be popped", |env| {
+    ///         assert_eq!(env.pop(), Some(3));
+    ///         assert_eq!(env.len(), 2);
+    ///     });
+    ///
+    ///     ctx.example("is left untouched by other examples", |env| env.len() == 3);
+    /// }));
+    /// # }
+    /// ```
+    ///
+    /// Corresponding console output:
+    ///
+    /// ```text
+    /// tests:
+    /// Suite "a vector":
+    ///     Example "can be popped" ... ok
+    ///     Example "is left untouched by other examples" ... ok
+    /// ```
+    ///
+    /// Available aliases:
+    ///
+    /// - [`it_mut`](struct.Context.html#method.it_mut).
+    /// - [`then_mut`](struct.Context.html#method.then_mut).
+    #[track_caller]
+    pub fn example_mut<F, U>(&mut self, name: &'static str, body: F)
+    where
+        F: 'static + Send + Sync + Fn(&mut T) -> U,
+        U: Into<ExampleResult>,
+    {
+        let header = ExampleHeader::new(ExampleLabel::Example, name);
+        self.example_internal(header, Mark::Default, body)
+    }
+
+    /// Alias for [`example_mut`](struct.Context.html#method.example_mut), see for more info.
+    ///
+    /// Available further aliases:
+    ///
+    /// - [`then_mut`](struct.Context.html#method.then_mut).
+    #[track_caller]
+    pub fn it_mut<F, U>(&mut self, name: &'static str, body: F)
+    where
+        F: 'static + Send + Sync + Fn(&mut T) -> U,
+        U: Into<ExampleResult>,
+    {
+        let header = ExampleHeader::new(ExampleLabel::It, name);
+        self.example_internal(header, Mark::Default, body)
+    }
+
+    /// Alias for [`example_mut`](struct.Context.html#method.example_mut), see for more info.
+    ///
+    /// Available further aliases:
+    ///
+    /// - [`it_mut`](struct.Context.html#method.it_mut).
+    #[track_caller]
+    pub fn then_mut<F, U>(&mut self, name: &'static str, body: F)
+    where
+        F: 'static + Send + Sync + Fn(&mut T) -> U,
+        U: Into<ExampleResult>,
+    {
+        let header = ExampleHeader::new(ExampleLabel::Then, name);
+        self.example_internal(header, Mark::Default, body)
+    }
+
+    /// Open and name a new focused example within the current context,
+    /// whose body gets mutable access to the environment
+    /// (see [`example_mut`](struct.Context.html#method.example_mut)
+    /// and [`fexample`](struct.Context.html#method.fexample) for more info).
+    ///
+    /// Available aliases:
+    ///
+    /// - [`fit_mut`](struct.Context.html#method.fit_mut).
+    /// - [`fthen_mut`](struct.Context.html#method.fthen_mut).
+    #[track_caller]
+    pub fn fexample_mut<F, U>(&mut self, name: &'static str, body: F)
+    where
+        F: 'static + Send + Sync + Fn(&mut T) -> U,
+        U: Into<ExampleResult>,
+    {
+        let header = ExampleHeader::new(ExampleLabel::Example, name);
+        self.example_internal(header, Mark::Focused, body)
+    }
+
+    /// Alias for [`fexample_mut`](struct.Context.html#method.fexample_mut), see for more info.
+    ///
+    /// Available further aliases:
+    ///
+    /// - [`fthen_mut`](struct.Context.html#method.fthen_mut).
+    #[track_caller]
+    pub fn fit_mut<F, U>(&mut self, name: &'static str, body: F)
+    where
+        F: 'static + Send + Sync + Fn(&mut T) -> U,
+        U: Into<ExampleResult>,
+    {
+        let header = ExampleHeader::new(ExampleLabel::It, name);
+        self.example_internal(header, Mark::Focused, body)
+    }
+
+    /// Alias for [`fexample_mut`](struct.Context.html#method.fexample_mut), see for more info.
+    ///
+    /// Available further aliases:
+    ///
+    /// - [`fit_mut`](struct.Context.html#method.fit_mut).
+    #[track_caller]
+    pub fn fthen_mut<F, U>(&mut self, name: &'static str, body: F)
+    where
+        F: 'static + Send + Sync + Fn(&mut T) -> U,
+        U: Into<ExampleResult>,
+    {
+        let header = ExampleHeader::new(ExampleLabel::Then, name);
+        self.example_internal(header, Mark::Focused, body)
+    }
+
+    /// Open and name a new pending example within the current context,
+    /// whose body gets mutable access to the environment
+    /// (see [`example_mut`](struct.Context.html#method.example_mut)
+    /// and [`xexample`](struct.Context.html#method.xexample) for more info).
+    ///
+    /// Available aliases:
+    ///
+    /// - [`xit_mut`](struct.Context.html#method.xit_mut).
+    /// - [`xthen_mut`](struct.Context.html#method.xthen_mut).
+    #[track_caller]
+    pub fn xexample_mut<F, U, S>(&mut self, name: &'static str, reason: S, body: F)
+    where
+        F: 'static + Send + Sync + Fn(&mut T) -> U,
+        U: Into<ExampleResult>,
+        S: Into<String>,
+    {
+        let header = ExampleHeader::new(ExampleLabel::Example, name);
+        self.example_internal(header, Mark::Pending(Some(reason.into())), body)
+    }
+
+    /// Alias for [`xexample_mut`](struct.Context.html#method.xexample_mut), see for more info.
+    ///
+    /// Available further aliases:
+    ///
+    /// - [`xthen_mut`](struct.Context.html#method.xthen_mut).
+    #[track_caller]
+    pub fn xit_mut<F, U, S>(&mut self, name: &'static str, reason: S, body: F)
+    where
+        F: 'static + Send + Sync + Fn(&mut T) -> U,
+        U: Into<ExampleResult>,
+        S: Into<String>,
+    {
+        let header = ExampleHeader::new(ExampleLabel::It, name);
+        self.example_internal(header, Mark::Pending(Some(reason.into())), body)
+    }
+
+    /// Alias for [`xexample_mut`](struct.Context.html#method.xexample_mut), see for more info.
+    ///
+    /// Available further aliases:
+    ///
+    /// - [`xit_mut`](struct.Context.html#method.xit_mut).
+    #[track_caller]
+    pub fn xthen_mut<F, U, S>(&mut self, name: &'static str, reason: S, body: F)
+    where
+        F: 'static + Send + Sync + Fn(&mut T) -> U,
+        U: Into<ExampleResult>,
+        S: Into<String>,
+    {
+        let header = ExampleHeader::new(ExampleLabel::Then, name);
+        self.example_internal(header, Mark::Pending(Some(reason.into())), body)
+    }
+
+    /// Declare a body-less example within the current context,
+    /// that gets reported as pending with the given reason.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// runner.run(&rspec::suite("a test suite", (), |ctx| {
+    ///     ctx.pending("an example", "waiting for upstream fix");
+    /// }));
+    /// # }
+    /// ```
+    ///
+    /// Corresponding console output:
+    ///
+    /// ```text
+    /// tests:
+    /// Suite "a test suite":
+    ///     Example "an example" ... pending (waiting for upstream fix)
+    /// ```
+    #[track_caller]
+    pub fn pending<S>(&mut self, name: &'static str, reason: S)
+    where
+        S: Into<String>,
+    {
+        let header = ExampleHeader::new(ExampleLabel::Example, name);
+        self.todo_internal(header, Some(reason.into()))
+    }
+
+    /// Declare a body-less example within the current context,
+    /// that gets reported as pending until it gets implemented.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// runner.run(&rspec::suite("a test suite", (), |ctx| {
+    ///     ctx.todo("an example yet to be written");
+    /// }));
+    /// # }
+    /// ```
+    ///
+    /// Corresponding console output:
     ///
-    /// - [`it`](struct.Context.html#method.it).
-    pub fn it<F, U>(&mut self, name: &'static str, body: F)
-    where
-        F: 'static + Fn(&T) -> U,
-        U: Into<ExampleResult>,
-    {
-        let header = ExampleHeader::new(ExampleLabel::It, name);
-        self.example_internal(header, body)
+    /// ```text
+    /// tests:
+    /// Suite "a test suite":
+    ///     Example "an example yet to be written" ... pending
+    /// ```
+    #[track_caller]
+    pub fn todo(&mut self, name: &'static str) {
+        let header = ExampleHeader::new(ExampleLabel::Example, name);
+        self.todo_internal(header, None)
     }
 
-    /// Alias for [`example`](struct.Context.html#method.example), see for more info.
-    ///
-    /// Available further aliases:
-    ///
-    /// - [`it`](struct.Context.html#method.it).
-    pub fn then<F, U>(&mut self, name: &'static str, body: F)
-    where
-        F: 'static + Fn(&T) -> U,
-        U: Into<ExampleResult>,
-    {
-        let header = ExampleHeader::new(ExampleLabel::Then, name);
-        self.example_internal(header, body)
+    #[track_caller]
+    fn todo_internal(&mut self, mut header: ExampleHeader, reason: Option<String>) {
+        header.location = Some(Location::caller());
+        let result = ExampleResult::Pending(reason.clone());
+        let mut example = Example::new(header, move |_| result.clone());
+        example.mark = Mark::Pending(reason);
+        self.blocks.push(Block::Example(example))
     }
 
-    fn example_internal<F, U>(&mut self, header: ExampleHeader, body: F)
+    #[track_caller]
+    fn example_internal<F, U>(&mut self, mut header: ExampleHeader, mark: Mark, body: F)
     where
-        F: 'static + Fn(&T) -> U,
+        F: 'static + Send + Sync + Fn(&mut T) -> U,
         U: Into<ExampleResult>,
     {
         use std::panic::{catch_unwind, AssertUnwindSafe};
 
-        let example = Example::new(header, move |environment| {
-            let result = catch_unwind(AssertUnwindSafe(|| body(&environment).into()));
-            match result {
-                Ok(result) => result,
-                Err(error) => {
-                    use std::borrow::Cow;
-                    let error_as_str = error.downcast_ref::<&str>().map(|s| Cow::from(*s));
-                    let error_as_string =
-                        error.downcast_ref::<String>().map(|s| Cow::from(s.clone()));
-                    let message = error_as_str
-                        .or(error_as_string)
-                        .map(|cow| format!("thread panicked at '{:?}'.", cow.to_string()));
-                    ExampleResult::Failure(message)
-                }
-            }
+        header.location = Some(Location::caller());
+        let mut example = Example::new(header, move |environment| {
+            let result = catch_unwind(AssertUnwindSafe(|| body(environment).into()));
+            result.unwrap_or_else(panics::failure)
         });
+        example.mark = mark;
         self.blocks.push(Block::Example(example))
     }
 
@@ -308,6 +868,11 @@ where
     ///
     /// Note that the order of execution **IS NOT** guaranteed to match the declaration order.
     ///
+    /// If the closure panics, none of the context's examples get executed
+    /// and all of them get reported as failed (see [`HookFailure`](../report/struct.HookFailure.html)).
+    /// Closures that can fail otherwise may be declared with
+    /// [`try_before_all`](struct.Context.html#method.try_before_all) instead.
+    ///
     /// # Examples
     ///
     /// ```
@@ -355,7 +920,10 @@ where
     where
         F: 'static + Fn(&mut T),
     {
-        self.before_all.push(Box::new(body))
+        self.before_all.push(Box::new(move |environment| {
+            body(environment);
+            ExampleResult::Success
+        }))
     }
 
     /// Alias for [`before_all`](struct.Context.html#method.before_all), see for more info.
@@ -371,6 +939,9 @@ where
     ///
     /// Note that the order of execution **IS NOT** guaranteed to match the declaration order.
     ///
+    /// If the closure panics, the child it got executed for gets reported as failed
+    /// (see [`try_before_each`](struct.Context.html#method.try_before_each) for closures that can fail otherwise).
+    ///
     /// # Examples
     ///
     /// ```
@@ -414,7 +985,10 @@ where
     where
         F: 'static + Fn(&mut T),
     {
-        self.before_each.push(Box::new(body))
+        self.before_each.push(Box::new(move |environment| {
+            body(environment);
+            ExampleResult::Success
+        }))
     }
 
     /// Declares a closure that will be executed once after any
@@ -422,6 +996,11 @@ where
     ///
     /// Note that the order of execution **IS NOT** guaranteed to match the declaration order.
     ///
+    /// If the closure panics, the context gets reported as failed
+    /// (see [`ContextReport::get_hook_failures`](../report/struct.ContextReport.html#method.get_hook_failures)).
+    /// Closures that can fail otherwise may be declared with
+    /// [`try_after_all`](struct.Context.html#method.try_after_all) instead.
+    ///
     /// # Examples
     ///
     /// ```
@@ -469,7 +1048,10 @@ where
     where
         F: 'static + Fn(&mut T),
     {
-        self.after_all.push(Box::new(body))
+        self.after_all.push(Box::new(move |environment| {
+            body(environment);
+            ExampleResult::Success
+        }))
     }
 
     /// Alias for [`after_all`](struct.Context.html#method.after_all), see for more info.
@@ -485,6 +1067,9 @@ where
     ///
     /// Note that the order of execution **IS NOT** guaranteed to match the declaration order.
     ///
+    /// If the closure panics, the child it got executed for gets reported as failed
+    /// (see [`try_after_each`](struct.Context.html#method.try_after_each) for closures that can fail otherwise).
+    ///
     /// # Examples
     ///
     /// ```
@@ -528,7 +1113,303 @@ where
     where
         F: 'static + Fn(&mut T),
     {
-        self.after_each.push(Box::new(body))
+        self.after_each.push(Box::new(move |environment| {
+            body(environment);
+            ExampleResult::Success
+        }))
+    }
+
+    /// Declares a closure that will be executed once before any
+    /// of the context's children, just like [`before_all`](struct.Context.html#method.before_all),
+    /// that can fail without panicking.
+    ///
+    /// Just like examples, the closure may return anything convertible into
+    /// an [`ExampleResult`](../report/enum.ExampleResult.html) (e.g. `bool` or `Result`).
+    /// If it fails, none of the context's examples get executed and all of them
+    /// get reported as failed, along with the error's message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// runner.run(&rspec::suite("a test suite", (), |ctx| {
+    ///     ctx.try_before_all(|_env| -> Result<(), String> {
+    ///         // …
+    ///         Ok(())
+    ///     });
+    ///
+    ///     ctx.example("an example", |_env| {
+    ///         // …
+    ///     });
+    /// }));
+    /// # }
+    /// ```
+    pub fn try_before_all<F, U>(&mut self, body: F)
+    where
+        F: 'static + Fn(&mut T) -> U,
+        U: Into<ExampleResult>,
+    {
+        self.before_all
+            .push(Box::new(move |environment| body(environment).into()))
+    }
+
+    /// Declares a closure that will be executed once before each
+    /// of the context's children, just like [`before_each`](struct.Context.html#method.before_each),
+    /// that can fail without panicking
+    /// (see [`try_before_all`](struct.Context.html#method.try_before_all)).
+    pub fn try_before_each<F, U>(&mut self, body: F)
+    where
+        F: 'static + Fn(&mut T) -> U,
+        U: Into<ExampleResult>,
+    {
+        self.before_each
+            .push(Box::new(move |environment| body(environment).into()))
+    }
+
+    /// Declares a closure that will be executed once after any
+    /// of the context's children, just like [`after_all`](struct.Context.html#method.after_all),
+    /// that can fail without panicking
+    /// (see [`try_before_all`](struct.Context.html#method.try_before_all)).
+    pub fn try_after_all<F, U>(&mut self, body: F)
+    where
+        F: 'static + Fn(&mut T) -> U,
+        U: Into<ExampleResult>,
+    {
+        self.after_all
+            .push(Box::new(move |environment| body(environment).into()))
+    }
+
+    /// Declares a closure that will be executed once after each
+    /// of the context's children, just like [`after_each`](struct.Context.html#method.after_each),
+    /// that can fail without panicking
+    /// (see [`try_before_all`](struct.Context.html#method.try_before_all)).
+    pub fn try_after_each<F, U>(&mut self, body: F)
+    where
+        F: 'static + Fn(&mut T) -> U,
+        U: Into<ExampleResult>,
+    {
+        self.after_each
+            .push(Box::new(move |environment| body(environment).into()))
+    }
+
+    /// Declares a closure that will be executed once around all
+    /// of the context's children (context or example blocks),
+    /// which get executed when the closure calls the block it is given.
+    ///
+    /// Around hooks suit setups that have to stay on the stack while the children get executed,
+    /// e.g. guards or spans. They get executed around the context's
+    /// [`before_all`](struct.Context.html#method.before_all)
+    /// and [`after_all`](struct.Context.html#method.after_all) hooks,
+    /// the first declared one being the outermost.
+    ///
+    /// The block is only executed once, however many times it gets called.
+    /// If the closure fails (or panics) without calling it, or doesn't call it at all,
+    /// none of the context's examples get executed and all of them get reported as failed
+    /// (see [`before_all`](struct.Context.html#method.before_all) for what closures may return).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::{Arc, Mutex};
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// let lock = Arc::new(Mutex::new(()));
+    ///
+    /// runner.run(&rspec::suite("a test suite", (), |ctx| {
+    ///     ctx.around_all(move |env, block| {
+    ///         let _guard = lock.lock().unwrap();
+    ///         block(env);
+    ///     });
+    ///
+    ///     ctx.example("an example", |_env| {
+    ///         // …
+    ///     });
+    ///
+    ///     ctx.example("another example", |_env| {
+    ///         // …
+    ///     });
+    /// }));
+    /// # }
+    /// ```
+    ///
+    /// Corresponding console output:
+    ///
+    /// ```text
+    /// tests:
+    /// Suite "a test suite":
+    ///     Example "an example":
+    ///         …
+    ///     Example "another example":
+    ///         …
+    /// ```
+    pub fn around_all<F, U>(&mut self, body: F)
+    where
+        F: 'static + Fn(&mut T, &mut dyn FnMut(&mut T)) -> U,
+        U: Into<ExampleResult>,
+    {
+        self.around_all.push(Box::new(move |environment, block| {
+            body(environment, block).into()
+        }))
+    }
+
+    /// Declares a closure that will be executed around each
+    /// of the context's children (context or example blocks),
+    /// which get executed when the closure calls the block it is given.
+    ///
+    /// Around hooks get executed around the context's
+    /// [`before_each`](struct.Context.html#method.before_each)
+    /// and [`after_each`](struct.Context.html#method.after_each) hooks,
+    /// the first declared one being the outermost.
+    /// Those of enclosing contexts get executed around those of nested contexts.
+    ///
+    /// If the closure fails (or panics), or doesn't call its block,
+    /// the child it got executed for gets reported as failed
+    /// (see [`around_all`](struct.Context.html#method.around_all)).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::env;
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// runner.run(&rspec::suite("a test suite", (), |ctx| {
+    ///     ctx.around_each(|env, block| {
+    ///         let previous = env::var("LANG");
+    ///         env::set_var("LANG", "C");
+    ///         block(env);
+    ///         match previous {
+    ///             Ok(lang) => env::set_var("LANG", lang),
+    ///             Err(_) => env::remove_var("LANG"),
+    ///         }
+    ///     });
+    ///
+    ///     ctx.example("an example", |_env| {
+    ///         // …
+    ///     });
+    /// }));
+    /// # }
+    /// ```
+    ///
+    /// Corresponding console output:
+    ///
+    /// ```text
+    /// tests:
+    /// Suite "a test suite":
+    ///     Example "an example":
+    ///         …
+    /// ```
+    pub fn around_each<F, U>(&mut self, body: F)
+    where
+        F: 'static + Fn(&mut T, &mut dyn FnMut(&mut T)) -> U,
+        U: Into<ExampleResult>,
+    {
+        self.around_each.push(Box::new(move |environment, block| {
+            body(environment, block).into()
+        }))
+    }
+
+    /// Limits the time each of the context's examples (including those of nested contexts)
+    /// is allowed to take, overriding [`Configuration.timeout`](../runner/struct.Configuration.html#structfield.timeout).
+    ///
+    /// Examples exceeding their timeout get reported as timed out, either right away
+    /// or only once they returned (see [`Runner::run`](../runner/struct.Runner.html#method.run)).
+    /// A single example can be given its own timeout by wrapping it in a [`scope`](#method.scope).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// runner.run(&rspec::suite("a test suite", (), |ctx| {
+    ///     ctx.timeout(rspec::report::Duration::seconds(5));
+    ///
+    ///     ctx.example("an example", |_env| {
+    ///         // …
+    ///     });
+    ///
+    ///     ctx.scope(|ctx| {
+    ///         ctx.timeout(rspec::report::Duration::seconds(30));
+    ///
+    ///         ctx.example("a slow example", |_env| {
+    ///             // …
+    ///         });
+    ///     });
+    /// }));
+    /// # }
+    /// ```
+    pub fn timeout(&mut self, timeout: Duration) {
+        self.timeout = Some(timeout);
+    }
+
+    /// Sets how many times each of the context's examples (including those of nested contexts)
+    /// gets re-executed upon failing, overriding [`Configuration.retries`](../runner/struct.Configuration.html#structfield.retries).
+    ///
+    /// Each attempt gets its own `before_each`/`after_each` hooks applied.
+    /// Examples only succeeding after having been retried get reported as flaky.
+    /// A single example can be given its own retry count by wrapping it in a [`scope`](#method.scope).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// runner.run(&rspec::suite("a test suite", (), |ctx| {
+    ///     ctx.example("an example", |_env| {
+    ///         // …
+    ///     });
+    ///
+    ///     ctx.scope(|ctx| {
+    ///         ctx.retries(3);
+    ///
+    ///         ctx.example("a flaky example", |_env| {
+    ///             // …
+    ///         });
+    ///     });
+    /// }));
+    /// # }
+    /// ```
+    pub fn retries(&mut self, retries: u32) {
+        self.retries = Some(retries);
     }
 }
 
@@ -563,6 +1444,11 @@ mod tests {
                 ctx.$context("context (or alias)", |_| {})
             });
         };
+        ($suite: ident, $context: ident, $reason: expr) => {
+            $suite("suite (or alias)", (), |ctx| {
+                ctx.$context("context (or alias)", $reason, |_| {})
+            });
+        };
     }
 
     #[test]
@@ -586,6 +1472,20 @@ mod tests {
         test_context_alias!(given, when);
     }
 
+    #[test]
+    fn it_has_pending_contextual_functions() {
+        test_context_alias!(suite, xcontext, "reason");
+        test_context_alias!(describe, xspecify, "reason");
+        test_context_alias!(given, xwhen, "reason");
+    }
+
+    #[test]
+    fn it_has_focused_contextual_functions() {
+        test_context_alias!(suite, fcontext);
+        test_context_alias!(describe, fspecify);
+        test_context_alias!(given, fwhen);
+    }
+
     macro_rules! test_example_alias {
         ($suite: ident, $context: ident, $example: ident) => {
             $suite("suite (or alias)", (), |ctx| {
@@ -594,6 +1494,13 @@ mod tests {
                 });
             });
         };
+        ($suite: ident, $context: ident, $example: ident, $reason: expr) => {
+            $suite("suite (or alias)", (), |ctx| {
+                ctx.$context("context (or alias)", |ctx| {
+                    ctx.$example("example (or alias)", $reason, |_| {});
+                });
+            });
+        };
     }
 
     #[test]
@@ -626,6 +1533,60 @@ mod tests {
         test_example_alias!(given, when, it);
     }
 
+    #[test]
+    fn it_has_focused_check_functions() {
+        test_example_alias!(suite, context, fexample);
+        test_example_alias!(describe, specify, fit);
+        test_example_alias!(given, when, fthen);
+    }
+
+    #[test]
+    fn it_has_pending_check_functions() {
+        test_example_alias!(suite, context, xexample, "reason");
+        test_example_alias!(describe, specify, xit, "reason");
+        test_example_alias!(given, when, xthen, "reason");
+    }
+
+    #[test]
+    fn it_has_mutable_check_functions() {
+        test_example_alias!(suite, context, example_mut);
+        test_example_alias!(describe, specify, it_mut);
+        test_example_alias!(given, when, then_mut);
+        test_example_alias!(suite, context, fexample_mut);
+        test_example_alias!(describe, specify, fit_mut);
+        test_example_alias!(given, when, fthen_mut);
+        test_example_alias!(suite, context, xexample_mut, "reason");
+        test_example_alias!(describe, specify, xit_mut, "reason");
+        test_example_alias!(given, when, xthen_mut, "reason");
+    }
+
+    #[test]
+    fn it_has_bodyless_check_functions() {
+        let suite = suite("suite", (), |ctx| {
+            ctx.pending("example", "reason");
+            ctx.todo("example");
+        });
+        assert_eq!(suite.num_examples(), 2);
+    }
+
+    #[test]
+    fn it_tracks_focus() {
+        let unfocused = suite("suite", (), |ctx| {
+            ctx.it("example", |_| {});
+        });
+        assert!(!unfocused.is_focused());
+        let focused_example = suite("suite", (), |ctx| {
+            ctx.context("context", |ctx| {
+                ctx.fit("example", |_| {});
+            });
+        });
+        assert!(focused_example.is_focused());
+        let focused_context = suite("suite", (), |ctx| {
+            ctx.fcontext("context", |_| {});
+        });
+        assert!(focused_context.is_focused());
+    }
+
     #[test]
     fn it_has_check_function_then() {
         test_example_alias!(suite, context, then);
@@ -0,0 +1,60 @@
+/// How the blocks of a suite get their environments.
+pub(crate) enum Environment<T> {
+    /// Each block gets a clone of its parent's environment, starting with the suite's
+    /// (see [`suite`](../fn.suite.html)).
+    Cloned(T, fn(&T) -> T),
+    /// Each block gets an environment of its own, built by the factory
+    /// (see [`suite_with`](../fn.suite_with.html)).
+    Built(Box<dyn Fn() -> T + Send + Sync>),
+}
+
+impl<T> Environment<T> {
+    pub(crate) fn cloned(environment: T) -> Self
+    where
+        T: Clone,
+    {
+        Environment::Cloned(environment, T::clone)
+    }
+
+    pub(crate) fn built<F>(factory: F) -> Self
+    where
+        F: 'static + Send + Sync + Fn() -> T,
+    {
+        Environment::Built(Box::new(factory))
+    }
+
+    /// Returns the environment of the suite's root context.
+    pub(crate) fn root(&self) -> T {
+        match self {
+            Environment::Cloned(ref environment, clone) => clone(environment),
+            Environment::Built(ref factory) => factory(),
+        }
+    }
+
+    /// Returns the environment of a block, given the one of its parent.
+    pub(crate) fn fresh(&self, parent: &T) -> T {
+        match self {
+            Environment::Cloned(_, clone) => clone(parent),
+            Environment::Built(ref factory) => factory(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_clones_the_parent_environment() {
+        let environment = Environment::cloned(1);
+        assert_eq!(environment.root(), 1);
+        assert_eq!(environment.fresh(&2), 2);
+    }
+
+    #[test]
+    fn it_builds_fresh_environments() {
+        let environment = Environment::built(|| vec![1]);
+        assert_eq!(environment.root(), vec![1]);
+        assert_eq!(environment.fresh(&vec![1, 2]), vec![1]);
+    }
+}
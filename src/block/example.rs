@@ -1,20 +1,29 @@
+use std::sync::Arc;
+
+use block::Mark;
 use header::ExampleHeader;
 use report::ExampleResult;
 
+/// An example's function, which can be run on a thread of its own
+/// (see [`Runner::run_all`](../runner/struct.Runner.html#method.run_all)).
+pub(crate) type ExampleFunction<T> = Arc<dyn Fn(&mut T) -> ExampleResult + Send + Sync>;
+
 /// Test examples are the smallest unit of a testing framework, wrapping one or more assertions.
 pub struct Example<T> {
     pub(crate) header: ExampleHeader,
-    pub(crate) function: Box<dyn Fn(&T) -> ExampleResult>,
+    pub(crate) function: ExampleFunction<T>,
+    pub(crate) mark: Mark,
 }
 
 impl<T> Example<T> {
     pub(crate) fn new<F>(header: ExampleHeader, assertion: F) -> Self
     where
-        F: 'static + Fn(&T) -> ExampleResult,
+        F: 'static + Send + Sync + Fn(&mut T) -> ExampleResult,
     {
         Example {
             header,
-            function: Box::new(assertion),
+            function: Arc::new(assertion),
+            mark: Mark::Default,
         }
     }
 
@@ -1,13 +1,26 @@
 //! Blocks are used to build a tree structure of named tests and contextes.
 
 pub mod context;
+mod environment;
 pub mod example;
 pub mod suite;
 
 pub use block::context::*;
+pub(crate) use block::environment::Environment;
 pub use block::example::*;
 pub use block::suite::*;
 
+/// Marks set on blocks upon declaration, changing how the runner treats them.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) enum Mark {
+    /// The block gets executed as usual.
+    Default,
+    /// The block gets executed, while all non-focused blocks of its suite get filtered out.
+    Focused,
+    /// The block doesn't get executed, but reported as pending with an optional reason.
+    Pending(Option<String>),
+}
+
 /// Blocks are used to build a tree structure of named tests and contextes.
 pub enum Block<T> {
     Context(Context<T>),
@@ -21,6 +34,14 @@ impl<T> Block<T> {
             Block::Example(_) => 1,
         }
     }
+
+    /// Whether the block is focused or contains focused blocks.
+    pub fn is_focused(&self) -> bool {
+        match self {
+            Block::Context(ref context) => context.is_focused(),
+            Block::Example(ref example) => example.mark == Mark::Focused,
+        }
+    }
 }
 
 unsafe impl<T> Send for Block<T> where T: Send {}
@@ -1,15 +1,111 @@
-use block::Context;
+use std::collections::{HashMap, HashSet};
+use std::panic::Location;
+
+use block::{Block, Context, Environment};
 use header::{SuiteHeader, SuiteLabel};
 
 /// Test suites bundle a set of closely related test examples into a logical execution group.
-#[derive(new)]
 pub struct Suite<T> {
     pub(crate) header: SuiteHeader,
-    pub(crate) environment: T,
+    pub(crate) environment: Environment<T>,
     pub(crate) context: Context<T>,
 }
 
 impl<T> Suite<T> {
+    /// Creates a suite whose blocks get clones of their parent's environment,
+    /// starting with `environment` (see [`suite`](../fn.suite.html)).
+    pub fn new(header: SuiteHeader, environment: T, context: Context<T>) -> Self
+    where
+        T: Clone,
+    {
+        Self::with_environment(header, Environment::cloned(environment), context)
+    }
+
+    /// Creates a suite whose blocks get environments built by `factory`
+    /// (see [`suite_with`](../fn.suite_with.html)).
+    pub fn with_factory<F>(header: SuiteHeader, factory: F, context: Context<T>) -> Self
+    where
+        F: 'static + Send + Sync + Fn() -> T,
+    {
+        Self::with_environment(header, Environment::built(factory), context)
+    }
+
+    fn with_environment(
+        header: SuiteHeader,
+        environment: Environment<T>,
+        mut context: Context<T>,
+    ) -> Self {
+        let mut taken = HashSet::new();
+        Self::collect_names(&context, header.name, &mut taken);
+        Self::disambiguate_names(&mut context, header.name, &mut HashMap::new(), &mut taken);
+        Suite {
+            header,
+            environment,
+            context,
+        }
+    }
+
+    /// Collects the full names of the context's examples, as declared.
+    fn collect_names(context: &Context<T>, path: &str, names: &mut HashSet<String>) {
+        for block in &context.blocks {
+            match block {
+                Block::Example(ref example) => {
+                    names.insert(format!("{} > {}", path, example.header.name));
+                }
+                Block::Context(ref context) => {
+                    Self::collect_names(context, &Self::context_path(context, path), names);
+                }
+            }
+        }
+    }
+
+    /// Gives examples their full names, numbering those sharing the same full name
+    /// in declaration order, so that each of them can be selected individually (e.g. by `--exact`).
+    ///
+    /// Numbers making up a name that is already `taken` (e.g. by an example
+    /// declared as `"example (2)"`) get skipped.
+    fn disambiguate_names(
+        context: &mut Context<T>,
+        path: &str,
+        ordinals: &mut HashMap<String, usize>,
+        taken: &mut HashSet<String>,
+    ) {
+        for block in &mut context.blocks {
+            match block {
+                Block::Example(ref mut example) => {
+                    let full_name = format!("{} > {}", path, example.header.name);
+                    let ordinal = ordinals.entry(full_name.clone()).or_insert(0);
+                    *ordinal += 1;
+                    let unique_name = if *ordinal > 1 {
+                        loop {
+                            let unique_name = format!("{} ({})", full_name, ordinal);
+                            if taken.insert(unique_name.clone()) {
+                                break unique_name;
+                            }
+                            *ordinal += 1;
+                        }
+                    } else {
+                        full_name
+                    };
+                    example.header.full_name = Some(unique_name);
+                }
+                Block::Context(ref mut context) => {
+                    let path = Self::context_path(context, path);
+                    Self::disambiguate_names(context, &path, ordinals, taken);
+                }
+            }
+        }
+    }
+
+    /// Returns the path of a context's blocks, given the one of the context.
+    fn context_path(context: &Context<T>, path: &str) -> String {
+        // Name-less contexts don't show up in full names:
+        match context.header {
+            Some(ref header) => format!("{} > {}", path, header.name),
+            None => path.to_owned(),
+        }
+    }
+
     pub fn num_blocks(&self) -> usize {
         self.context.num_blocks()
     }
@@ -21,6 +117,11 @@ impl<T> Suite<T> {
     pub fn is_empty(&self) -> bool {
         self.context.is_empty()
     }
+
+    /// Whether the suite contains focused blocks.
+    pub fn is_focused(&self) -> bool {
+        self.context.is_focused()
+    }
 }
 
 unsafe impl<T> Send for Suite<T> where T: Send {}
@@ -59,16 +160,14 @@ unsafe impl<T> Sync for Suite<T> where T: Sync {}
 ///
 /// - [`describe`](fn.describe.html).
 /// - [`given`](fn.given.html).
+#[track_caller]
 pub fn suite<F, T>(name: &'static str, environment: T, body: F) -> Suite<T>
 where
     F: FnOnce(&mut Context<T>),
     T: Clone + ::std::fmt::Debug,
 {
-    let header = SuiteHeader {
-        label: SuiteLabel::Suite,
-        name,
-    };
-    suite_internal(header, environment, body)
+    let header = SuiteHeader::new(SuiteLabel::Suite, name);
+    suite_internal(header, Environment::cloned(environment), body)
 }
 
 /// Alias for [`suite`](fn.suite.html), see for more info.
@@ -76,16 +175,14 @@ where
 /// Available further aliases:
 ///
 /// - [`given`](fn.describe.html).
+#[track_caller]
 pub fn describe<F, T>(name: &'static str, environment: T, body: F) -> Suite<T>
 where
     F: FnOnce(&mut Context<T>),
     T: Clone + ::std::fmt::Debug,
 {
-    let header = SuiteHeader {
-        label: SuiteLabel::Describe,
-        name,
-    };
-    suite_internal(header, environment, body)
+    let header = SuiteHeader::new(SuiteLabel::Describe, name);
+    suite_internal(header, Environment::cloned(environment), body)
 }
 
 /// Alias for [`suite`](fn.suite.html), see for more info.
@@ -93,26 +190,120 @@ where
 /// Available further aliases:
 ///
 /// - [`describe`](fn.describe.html).
+#[track_caller]
 pub fn given<F, T>(name: &'static str, environment: T, body: F) -> Suite<T>
 where
     F: FnOnce(&mut Context<T>),
     T: Clone + ::std::fmt::Debug,
 {
-    let header = SuiteHeader {
-        label: SuiteLabel::Given,
-        name,
-    };
-    suite_internal(header, environment, body)
+    let header = SuiteHeader::new(SuiteLabel::Given, name);
+    suite_internal(header, Environment::cloned(environment), body)
 }
 
-fn suite_internal<F, T>(header: SuiteHeader, environment: T, body: F) -> Suite<T>
+/// Creates a test suite from a given root context, whose blocks get environments
+/// built by `factory` rather than clones of their parent's.
+///
+/// This allows for environments that can't be cloned, e.g. holding files, sockets or connections.
+/// Each context and each attempt of an example gets a fresh environment of its own,
+/// which gets set up by the `before_each` hooks of all of its enclosing contexts
+/// (from outer to inner) and torn down by their `after_each` hooks (from inner to outer).
+/// Changes made by `before_all` hooks don't carry over to a context's blocks,
+/// and `around_each` hooks only wrap the blocks of their own context.
+///
+/// As blocks may get evaluated in parallel, the factory itself has to be `Send` and `Sync`.
+/// The environments it builds don't: suites whose environments are neither `Send` nor `Sync`
+/// can still be run serially (see [`Runner::run_serial`](../struct.Runner.html#method.run_serial)),
+/// though not together with other suites (see [`RunnableSuite`](../trait.RunnableSuite.html)).
+///
+/// # Examples
+///
+/// ```
+/// # extern crate rspec;
+/// #
+/// # use std::io;
+/// # use std::sync::Arc;
+/// # use std::sync::atomic::{AtomicUsize, Ordering};
+/// #
+/// # pub fn main() {
+/// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+/// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+/// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+/// #
+/// runner.run(&rspec::suite_with("a test suite", || AtomicUsize::new(0), |ctx| {
+///     ctx.before_each(|env| {
+///         env.fetch_add(1, Ordering::SeqCst);
+///     });
+///
+///     ctx.example("an example", |env| env.load(Ordering::SeqCst) == 1);
+/// }));
+/// # }
+/// ```
+///
+/// Corresponding console output:
+///
+/// ```text
+/// tests
+/// Suite "a test suite":
+///     Example "an example" ... ok
+/// ```
+///
+/// Available aliases:
+///
+/// - [`describe_with`](fn.describe_with.html).
+/// - [`given_with`](fn.given_with.html).
+#[track_caller]
+pub fn suite_with<F, G, T>(name: &'static str, factory: G, body: F) -> Suite<T>
 where
     F: FnOnce(&mut Context<T>),
-    T: Clone + ::std::fmt::Debug,
+    G: 'static + Send + Sync + Fn() -> T,
+    T: ::std::fmt::Debug,
+{
+    let header = SuiteHeader::new(SuiteLabel::Suite, name);
+    suite_internal(header, Environment::built(factory), body)
+}
+
+/// Alias for [`suite_with`](fn.suite_with.html), see for more info.
+///
+/// Available further aliases:
+///
+/// - [`given_with`](fn.given_with.html).
+#[track_caller]
+pub fn describe_with<F, G, T>(name: &'static str, factory: G, body: F) -> Suite<T>
+where
+    F: FnOnce(&mut Context<T>),
+    G: 'static + Send + Sync + Fn() -> T,
+    T: ::std::fmt::Debug,
+{
+    let header = SuiteHeader::new(SuiteLabel::Describe, name);
+    suite_internal(header, Environment::built(factory), body)
+}
+
+/// Alias for [`suite_with`](fn.suite_with.html), see for more info.
+///
+/// Available further aliases:
+///
+/// - [`describe_with`](fn.describe_with.html).
+#[track_caller]
+pub fn given_with<F, G, T>(name: &'static str, factory: G, body: F) -> Suite<T>
+where
+    F: FnOnce(&mut Context<T>),
+    G: 'static + Send + Sync + Fn() -> T,
+    T: ::std::fmt::Debug,
+{
+    let header = SuiteHeader::new(SuiteLabel::Given, name);
+    suite_internal(header, Environment::built(factory), body)
+}
+
+#[track_caller]
+fn suite_internal<F, T>(mut header: SuiteHeader, environment: Environment<T>, body: F) -> Suite<T>
+where
+    F: FnOnce(&mut Context<T>),
+    T: ::std::fmt::Debug,
 {
+    header.location = Some(Location::caller());
     let mut ctx = Context::new(None);
     body(&mut ctx);
-    Suite::new(header, environment, ctx)
+    Suite::with_environment(header, environment, ctx)
 }
 
 #[cfg(test)]
@@ -124,7 +315,7 @@ mod tests {
         let suite = suite("name", (), |_| {});
         assert_eq!(suite.header.label, SuiteLabel::Suite);
         assert_eq!(suite.header.name, "name");
-        assert_eq!(suite.environment, ());
+        assert_eq!(suite.environment.root(), ());
         assert_eq!(suite.is_empty(), true);
         assert_eq!(suite.num_examples(), 0);
     }
@@ -134,7 +325,7 @@ mod tests {
         let describe = describe("name", (), |_| {});
         assert_eq!(describe.header.label, SuiteLabel::Describe);
         assert_eq!(describe.header.name, "name");
-        assert_eq!(describe.environment, ());
+        assert_eq!(describe.environment.root(), ());
         assert_eq!(describe.is_empty(), true);
         assert_eq!(describe.num_examples(), 0);
     }
@@ -144,7 +335,7 @@ mod tests {
         let given = given("name", (), |_| {});
         assert_eq!(given.header.label, SuiteLabel::Given);
         assert_eq!(given.header.name, "name");
-        assert_eq!(given.environment, ());
+        assert_eq!(given.environment.root(), ());
         assert_eq!(given.is_empty(), true);
         assert_eq!(given.num_examples(), 0);
     }
@@ -156,8 +347,19 @@ mod tests {
         });
         assert_eq!(suite.header.label, SuiteLabel::Suite);
         assert_eq!(suite.header.name, "suite");
-        assert_eq!(suite.environment, ());
+        assert_eq!(suite.environment.root(), ());
         assert_eq!(suite.is_empty(), false);
         assert_eq!(suite.num_examples(), 0);
     }
+
+    #[test]
+    fn factory_suites() {
+        let suite = suite_with("suite", || 42, |_| {});
+        assert_eq!(suite.header.label, SuiteLabel::Suite);
+        assert_eq!(suite.environment.root(), 42);
+        let describe = describe_with("describe", || 42, |_| {});
+        assert_eq!(describe.header.label, SuiteLabel::Describe);
+        let given = given_with("given", || 42, |_| {});
+        assert_eq!(given.header.label, SuiteLabel::Given);
+    }
 }
@@ -0,0 +1,591 @@
+//! Command-line arguments, for running suites from `harness = false` test targets
+//! just like regular cargo tests.
+//!
+//! The arguments mirror those of libtest (the default test harness), so that
+//! `cargo test -- <filter> --exact --skip <filter> --test-threads=1 --list` work as expected.
+//! Test names are the examples' full names, that is the names of their suite,
+//! their (named) ancestor contexts and their own name, joined by `" > "`.
+//! Examples sharing the same full name get numbered in declaration order
+//! (e.g. `"suite > example (2)"`), so that each test name is unique.
+//!
+//! This also makes test binaries work with [cargo-nextest](https://nexte.st),
+//! which lists tests with `--list --format terse` and then runs each of them
+//! in its own process with `--exact <name>`. Running a single example this way
+//! still runs the `before_all`/`before_each` (and `after_…`) hooks of its ancestor contexts.
+//! Likewise, `--format json` writes libtest's JSON events, as expected by IDEs' test explorers.
+//!
+//! Suites registered across a crate's modules can be run all at once as well
+//! (see [`registry`](../registry/index.html)), optionally selected by name with `--suite <name>`.
+//!
+//! Beyond libtest's arguments, `--at <file>:<line>` runs the examples declared at the given line,
+//! or within a suite or context declared there (e.g. `cargo test -- --at specs.rs:42`).
+//!
+//! # Examples
+//!
+//! ```toml
+//! # Cargo.toml
+//! [[test]]
+//! name = "specs"
+//! harness = false
+//! ```
+//!
+//! ```no_run
+//! // tests/specs.rs
+//! extern crate rspec;
+//!
+//! pub fn main() -> rspec::RunSummary {
+//!     rspec::cli::run(&rspec::describe("a test suite", (), |ctx| {
+//!         ctx.it("passes", |_env| true);
+//!     }))
+//! }
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::process;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use colored;
+
+use block::Suite;
+use logger::{LibtestJsonLogger, Logger};
+use registry;
+use runner::{
+    seed_from_env, ConfigurationBuilder, Filter, PendingMode, RunSummary, RunnableSuite, Runner,
+    RunnerObserver,
+};
+
+const USAGE: &str = "Usage: [OPTIONS] [FILTERS...]
+
+Options:
+        --include-ignored
+                        Run pending tests as well as regular ones
+        --ignored       Run only pending tests
+        --exclude-should-panic
+                        (accepted for compatibility, has no effect)
+        --test          Run tests (the default)
+        --list          List all tests instead of running them
+        --exact         Exactly match filters rather than by substring
+        --skip FILTER   Skip tests whose names contain FILTER
+                        (this flag can be used multiple times)
+        --suite NAME    Only run suites whose names contain NAME
+                        (this flag can be used multiple times)
+        --at FILE:LINE  Run tests declared at LINE of FILE, or within
+                        suites or contexts declared there
+                        (this flag can be used multiple times)
+        --test-threads n_threads
+                        Number of threads used for running tests in parallel
+        --nocapture, --show-output
+                        (accepted for compatibility, output is never captured)
+    -q, --quiet         Alias to --format=terse
+        --color auto|always|never
+                        Configure coloring of output
+        --format pretty|terse|json
+                        Configure formatting of output
+        --shuffle       Run tests in random order
+        --shuffle-seed SEED
+                        Run tests in random order, seeded with SEED
+                        (defaults to the RSPEC_SEED environment variable)
+    -Z unstable-options (accepted for compatibility, has no effect)
+    -h, --help          Display this message
+";
+
+/// How output gets colored (`--color`).
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ColorConfig {
+    /// Colors output if it is a terminal.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// How output gets formatted (`--format`).
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum OutputFormat {
+    #[default]
+    Pretty,
+    /// Omits the summary line from `--list`'s output (as expected by cargo-nextest).
+    Terse,
+    /// Writes libtest's JSON events (see [`LibtestJsonLogger`](../logger/struct.LibtestJsonLogger.html)).
+    Json,
+}
+
+/// The error returned for invalid command-line arguments.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ArgumentsError {
+    /// An option that isn't supported.
+    UnknownOption(String),
+    /// An option expecting a value, which is missing.
+    MissingValue(String),
+    /// An option with a value it doesn't support.
+    InvalidValue(String, String),
+}
+
+impl fmt::Display for ArgumentsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArgumentsError::UnknownOption(ref option) => {
+                write!(f, "Unrecognized option: '{}'", option)
+            }
+            ArgumentsError::MissingValue(ref option) => {
+                write!(f, "Argument to option '{}' missing", option)
+            }
+            ArgumentsError::InvalidValue(ref option, ref value) => {
+                write!(f, "Invalid value '{}' for option '{}'", value, option)
+            }
+        }
+    }
+}
+
+impl Error for ArgumentsError {}
+
+/// libtest-style command-line arguments.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Arguments {
+    /// Filters selecting the tests to run by (partial) name.
+    pub filters: Vec<String>,
+    /// Filters selecting the tests to skip by (partial) name (`--skip`).
+    pub skip: Vec<String>,
+    /// Filters selecting the suites to run by (partial) name (`--suite`).
+    pub suites: Vec<String>,
+    /// Source locations selecting the tests to run, as file and line (`--at`).
+    pub lines: Vec<(String, u32)>,
+    /// Whether filters have to match names exactly, rather than partially (`--exact`).
+    pub exact: bool,
+    /// Whether to list the tests, rather than running them (`--list`).
+    pub list: bool,
+    /// Whether to run pending tests (`--ignored`, `--include-ignored`).
+    pub pending_mode: PendingMode,
+    /// How many threads to run tests on (`--test-threads`).
+    pub test_threads: Option<usize>,
+    /// Whether to run tests in random order (`--shuffle`, `--shuffle-seed`).
+    pub shuffle: bool,
+    /// The seed for running tests in random order (`--shuffle-seed`).
+    pub shuffle_seed: Option<u64>,
+    /// How to color output (`--color`).
+    pub color: ColorConfig,
+    /// How to format output (`--format`, `--quiet`).
+    pub format: OutputFormat,
+    /// Whether to display the usage, rather than running the tests (`--help`).
+    pub help: bool,
+}
+
+impl Arguments {
+    /// Parses the current process' arguments (skipping the program's name),
+    /// taking the seed from the `RSPEC_SEED` environment variable unless given by `--shuffle-seed`
+    /// (see [`SEED_ENV_VAR`](../runner/constant.SEED_ENV_VAR.html)).
+    pub fn from_env() -> Result<Self, ArgumentsError> {
+        let mut arguments = Arguments::parse(::std::env::args().skip(1))?;
+        if arguments.shuffle_seed.is_none() {
+            arguments.shuffle_seed = seed_from_env();
+        }
+        Ok(arguments)
+    }
+
+    /// Parses the given arguments (not including the program's name).
+    pub fn parse<I, S>(args: I) -> Result<Self, ArgumentsError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut arguments = Arguments::default();
+        let mut args = args.into_iter().map(Into::into);
+        while let Some(arg) = args.next() {
+            if !arg.starts_with('-') || arg == "-" {
+                arguments.filters.push(arg);
+                continue;
+            }
+            // Options accept their value either inline (`--opt=value`) or as the next argument:
+            let (option, inline_value) = match arg.find('=') {
+                Some(index) if arg.starts_with("--") => {
+                    (arg[..index].to_owned(), Some(arg[index + 1..].to_owned()))
+                }
+                _ => (arg.clone(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| ArgumentsError::MissingValue(option.clone()))
+            };
+            match option.as_str() {
+                "--include-ignored" => arguments.pending_mode = PendingMode::Include,
+                "--ignored" => arguments.pending_mode = PendingMode::Only,
+                "--list" => arguments.list = true,
+                "--exact" => arguments.exact = true,
+                "--skip" => arguments.skip.push(value()?),
+                "--suite" => arguments.suites.push(value()?),
+                "--at" => arguments.lines.push(parse_line(&option, value()?)?),
+                "--test-threads" => arguments.test_threads = Some(parse_value(&option, value()?)?),
+                "--color" => {
+                    arguments.color = match value()?.as_str() {
+                        "auto" => ColorConfig::Auto,
+                        "always" => ColorConfig::Always,
+                        "never" => ColorConfig::Never,
+                        other => return Err(invalid_value(&option, other)),
+                    }
+                }
+                "--format" => {
+                    arguments.format = match value()?.as_str() {
+                        "pretty" => OutputFormat::Pretty,
+                        "terse" => OutputFormat::Terse,
+                        "json" => OutputFormat::Json,
+                        other => return Err(invalid_value(&option, other)),
+                    }
+                }
+                "-q" | "--quiet" => arguments.format = OutputFormat::Terse,
+                "--shuffle" => arguments.shuffle = true,
+                "--shuffle-seed" => {
+                    arguments.shuffle = true;
+                    arguments.shuffle_seed = Some(parse_value(&option, value()?)?);
+                }
+                "-h" | "--help" => arguments.help = true,
+                "--test" | "--nocapture" | "--show-output" | "--exclude-should-panic" => {}
+                "-Z" => {
+                    value()?;
+                }
+                _ => return Err(ArgumentsError::UnknownOption(arg)),
+            }
+        }
+        Ok(arguments)
+    }
+
+    /// Returns a configuration builder, set up according to the arguments.
+    pub fn configuration_builder(&self) -> ConfigurationBuilder {
+        let mut builder = ConfigurationBuilder::default();
+        if !self.filters.is_empty() || !self.lines.is_empty() {
+            builder.filter(self.filter());
+        }
+        builder
+            .skip(
+                self.skip
+                    .iter()
+                    .map(|skip| self.name_filter(skip))
+                    .collect(),
+            )
+            .pending_mode(self.pending_mode);
+        match self.test_threads {
+            Some(1) => {
+                builder.parallel(false);
+            }
+            Some(test_threads) => {
+                builder.parallel(true).test_threads(test_threads);
+            }
+            None => {}
+        }
+        if self.shuffle {
+            builder.random_order(true);
+        }
+        if let Some(seed) = self.shuffle_seed {
+            builder.seed(seed);
+        }
+        builder
+    }
+
+    /// Returns the filter selecting the tests to run, by name or line.
+    fn filter(&self) -> Filter {
+        let mut filters: Vec<_> = self
+            .filters
+            .iter()
+            .map(|pattern| self.name_filter(pattern))
+            .chain(
+                self.lines
+                    .iter()
+                    .map(|(file, line)| Filter::line(file.clone(), *line)),
+            )
+            .collect();
+        if filters.len() == 1 {
+            filters.remove(0)
+        } else {
+            Filter::any(filters)
+        }
+    }
+
+    /// Returns the filter matching names by `pattern`, exactly if `--exact` is given.
+    fn name_filter(&self, pattern: &str) -> Filter {
+        if self.exact {
+            Filter::exact(pattern)
+        } else {
+            Filter::substring(pattern)
+        }
+    }
+
+    /// Whether a suite is selected by the `--suite` arguments (if any),
+    /// matching exactly if `--exact` is given.
+    pub fn is_suite_selected(&self, name: &str) -> bool {
+        self.suites.is_empty()
+            || self.suites.iter().any(|pattern| {
+                if self.exact {
+                    name == pattern
+                } else {
+                    name.contains(pattern.as_str())
+                }
+            })
+    }
+
+    /// Applies the `--color` argument to all of the process' output.
+    pub fn apply_color(&self) {
+        match self.color {
+            ColorConfig::Auto => colored::control::unset_override(),
+            ColorConfig::Always => colored::control::set_override(true),
+            ColorConfig::Never => colored::control::set_override(false),
+        }
+    }
+
+    /// Writes the given test names the way `--list` does.
+    pub fn write_list<W: io::Write>(&self, buffer: &mut W, names: &[String]) -> io::Result<()> {
+        for name in names {
+            writeln!(buffer, "{}: test", name)?;
+        }
+        if self.format != OutputFormat::Terse {
+            writeln!(buffer)?;
+            writeln!(buffer, "{} tests, 0 benchmarks", names.len())?;
+        }
+        Ok(())
+    }
+}
+
+fn invalid_value(option: &str, value: &str) -> ArgumentsError {
+    ArgumentsError::InvalidValue(option.to_owned(), value.to_owned())
+}
+
+fn parse_value<T: FromStr>(option: &str, value: String) -> Result<T, ArgumentsError> {
+    value.parse().map_err(|_| invalid_value(option, &value))
+}
+
+fn parse_line(option: &str, value: String) -> Result<(String, u32), ArgumentsError> {
+    match value.rfind(':') {
+        Some(index) if index > 0 => match value[index + 1..].parse() {
+            Ok(line) => Ok((value[..index].to_owned(), line)),
+            Err(_) => Err(invalid_value(option, &value)),
+        },
+        _ => Err(invalid_value(option, &value)),
+    }
+}
+
+/// Runs a test suite according to the current process' command-line arguments
+/// (see [`Arguments`](struct.Arguments.html)), returning the run's outcome
+/// for the test binary to exit with.
+///
+/// Exits the process right away upon invalid arguments.
+pub fn run<T>(suite: &Suite<T>) -> RunSummary
+where
+    T: 'static + Send + Sync + ::std::fmt::Debug,
+{
+    run_all(&[suite])
+}
+
+/// Runs all registered test suites (see [`registry`](../registry/index.html))
+/// according to the current process' command-line arguments, just like [`run_all`](fn.run_all.html).
+pub fn run_registered() -> RunSummary {
+    let suites = registry::suites();
+    let suites: Vec<_> = suites.iter().map(|suite| suite.as_ref()).collect();
+    run_all(&suites)
+}
+
+/// Runs several test suites according to the current process' command-line arguments
+/// (see [`Arguments`](struct.Arguments.html)), as a single run (see [`Runner::run_all`](../struct.Runner.html#method.run_all)),
+/// returning the run's outcome for the test binary to exit with.
+///
+/// Exits the process right away upon invalid arguments.
+pub fn run_all(suites: &[&dyn RunnableSuite]) -> RunSummary {
+    let arguments = match Arguments::from_env() {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+            process::exit(101);
+        }
+    };
+    if arguments.help {
+        print!("{}", USAGE);
+        return RunSummary::default();
+    }
+    arguments.apply_color();
+
+    let suites: Vec<_> = suites
+        .iter()
+        .cloned()
+        .filter(|suite| arguments.is_suite_selected(suite.header().name))
+        .collect();
+    let configuration = arguments.configuration_builder().build().unwrap();
+    if arguments.list {
+        let runner = Runner::new(configuration, vec![]);
+        let names: Vec<_> = suites
+            .iter()
+            .flat_map(|suite| suite.list(&runner))
+            .collect();
+        let _ = arguments.write_list(&mut io::stdout(), &names);
+        return RunSummary::default();
+    }
+    let logger: Arc<dyn RunnerObserver> = match arguments.format {
+        OutputFormat::Json => Arc::new(LibtestJsonLogger::new(io::stdout())),
+        OutputFormat::Pretty | OutputFormat::Terse => Arc::new(Logger::new(io::stdout())),
+    };
+    let runner = Runner::new(configuration, vec![logger]);
+    runner.run_all(&suites);
+    runner.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Arguments {
+        Arguments::parse(args.iter().cloned()).unwrap()
+    }
+
+    #[test]
+    fn it_parses_nothing() {
+        assert_eq!(parse(&[]), Arguments::default());
+    }
+
+    #[test]
+    fn it_parses_filters() {
+        let arguments = parse(&["a", "--exact", "b", "--skip", "c", "--skip=d"]);
+        assert_eq!(arguments.filters, vec!["a", "b"]);
+        assert_eq!(arguments.skip, vec!["c", "d"]);
+        assert!(arguments.exact);
+    }
+
+    #[test]
+    fn it_parses_options() {
+        let arguments = parse(&[
+            "--list",
+            "--ignored",
+            "--test-threads",
+            "4",
+            "--color=never",
+            "--format",
+            "terse",
+            "--nocapture",
+            "-Z",
+            "unstable-options",
+            "--shuffle-seed=42",
+        ]);
+        assert!(arguments.list);
+        assert_eq!(arguments.pending_mode, PendingMode::Only);
+        assert_eq!(arguments.test_threads, Some(4));
+        assert_eq!(arguments.color, ColorConfig::Never);
+        assert_eq!(arguments.format, OutputFormat::Terse);
+        assert!(arguments.shuffle);
+        assert_eq!(arguments.shuffle_seed, Some(42));
+        assert_eq!(
+            parse(&["--include-ignored"]).pending_mode,
+            PendingMode::Include
+        );
+        assert_eq!(parse(&["-q"]).format, OutputFormat::Terse);
+        assert_eq!(
+            parse(&["-Z", "unstable-options", "--format=json"]).format,
+            OutputFormat::Json
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_arguments() {
+        let parse = |args: &[&str]| Arguments::parse(args.iter().cloned()).unwrap_err();
+        assert_eq!(
+            parse(&["--frobnicate"]),
+            ArgumentsError::UnknownOption("--frobnicate".to_owned())
+        );
+        assert_eq!(
+            parse(&["--skip"]),
+            ArgumentsError::MissingValue("--skip".to_owned())
+        );
+        assert_eq!(
+            parse(&["--at", "specs.rs"]),
+            ArgumentsError::InvalidValue("--at".to_owned(), "specs.rs".to_owned())
+        );
+        assert_eq!(
+            parse(&["--test-threads", "many"]),
+            ArgumentsError::InvalidValue("--test-threads".to_owned(), "many".to_owned())
+        );
+        assert_eq!(
+            parse(&["--format", "junit"]),
+            ArgumentsError::InvalidValue("--format".to_owned(), "junit".to_owned())
+        );
+    }
+
+    #[test]
+    fn it_selects_suites_by_name() {
+        let arguments = parse(&["--suite", "data", "--suite=http"]);
+        assert_eq!(arguments.suites, vec!["data", "http"]);
+        assert!(arguments.is_suite_selected("database"));
+        assert!(arguments.is_suite_selected("http client"));
+        assert!(!arguments.is_suite_selected("parser"));
+        assert!(parse(&[]).is_suite_selected("parser"));
+        assert!(!parse(&["--exact", "--suite", "data"]).is_suite_selected("database"));
+    }
+
+    #[test]
+    fn it_selects_tests_by_line() {
+        let arguments = parse(&["--at", "tests/specs.rs:42", "a"]);
+        assert_eq!(arguments.lines, vec![("tests/specs.rs".to_owned(), 42)]);
+        let filter = arguments.configuration_builder().build().unwrap().filter;
+        let filter = filter.unwrap();
+        let location = ::std::panic::Location::caller();
+        assert!(filter.is_match("a"));
+        assert!(!filter.is_match_at("b", &[location]));
+    }
+
+    #[test]
+    fn it_skips_tests_by_name_only() {
+        // arrange
+        let location = ::std::panic::Location::caller();
+        let at = format!("{}:{}", location.file(), location.line());
+        // act
+        let configuration = parse(&["--at", &at, "--skip", "slow"])
+            .configuration_builder()
+            .build()
+            .unwrap();
+        // assert
+        let filter = configuration.filter.unwrap();
+        assert!(filter.is_match_at("fast", &[location]));
+        assert_eq!(configuration.skip.len(), 1);
+        assert!(!configuration.skip[0].is_match_at("fast", &[location]));
+        assert!(configuration.skip[0].is_match_at("slow", &[location]));
+    }
+
+    #[test]
+    fn it_builds_a_configuration() {
+        let configuration = parse(&["a", "b", "--exact", "--skip", "c", "--test-threads=1"])
+            .configuration_builder()
+            .build()
+            .unwrap();
+        let filter = configuration.filter.unwrap();
+        assert!(filter.is_match("a"));
+        assert!(filter.is_match("b"));
+        assert!(!filter.is_match("ab"));
+        assert!(configuration.skip[0].is_match("c"));
+        assert!(!configuration.skip[0].is_match("cd"));
+        assert_eq!(configuration.parallel, false);
+
+        let configuration = parse(&["--test-threads=4"])
+            .configuration_builder()
+            .build()
+            .unwrap();
+        assert_eq!(configuration.parallel, true);
+        assert_eq!(configuration.test_threads, Some(4));
+    }
+
+    #[test]
+    fn it_writes_lists() {
+        let names = vec!["suite > a".to_owned(), "suite > b".to_owned()];
+        let mut buffer = vec![];
+        parse(&["--list"]).write_list(&mut buffer, &names).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "suite > a: test\nsuite > b: test\n\n2 tests, 0 benchmarks\n"
+        );
+        let mut buffer = vec![];
+        parse(&["--list", "--format=terse"])
+            .write_list(&mut buffer, &names)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "suite > a: test\nsuite > b: test\n"
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::panic::Location;
 
 /// How the [`Context`](../block/struct.Context.html) will be printed by the [`Logger`](../logger/index.html).
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -23,6 +24,9 @@ impl fmt::Display for ContextLabel {
 pub struct ContextHeader {
     pub label: ContextLabel,
     pub name: &'static str,
+    /// Where the block got declared, if known.
+    #[new(default)]
+    pub location: Option<&'static Location<'static>>,
 }
 
 impl fmt::Display for ContextHeader {
@@ -39,7 +43,7 @@ mod tests {
     fn label_fmt() {
         fn subject(label: ContextLabel) -> String {
             format!("{}", label)
-        };
+        }
         assert_eq!(subject(ContextLabel::Context), "Context".to_owned());
         assert_eq!(subject(ContextLabel::Specify), "Specify".to_owned());
         assert_eq!(subject(ContextLabel::When), "When".to_owned());
@@ -49,7 +53,7 @@ mod tests {
     fn header_fmt() {
         fn subject(label: ContextLabel) -> String {
             format!("{}", ContextHeader::new(label, "Test"))
-        };
+        }
         assert_eq!(
             subject(ContextLabel::Context),
             "Context \"Test\"".to_owned()
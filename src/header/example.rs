@@ -1,4 +1,5 @@
 use std::fmt;
+use std::panic::Location;
 
 /// How the [`Example`](../block/struct.Example.html) will be printed by the [`Logger`](../logger/index.html).
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -23,6 +24,26 @@ impl fmt::Display for ExampleLabel {
 pub struct ExampleHeader {
     pub label: ExampleLabel,
     pub name: &'static str,
+    /// Where the block got declared, if known.
+    #[new(default)]
+    pub location: Option<&'static Location<'static>>,
+    /// The example's full name, unique within its suite, if known (see [`full_name`](#method.full_name)).
+    #[new(default)]
+    pub(crate) full_name: Option<String>,
+}
+
+impl ExampleHeader {
+    /// Returns the names of the example's suite, its (named) ancestor contexts and its own name,
+    /// joined by `" > "` and numbered if needed to tell it apart from preceding examples
+    /// of the same full name (e.g. `"suite > context > example (2)"`).
+    ///
+    /// Examples not (yet) part of a suite only have their own name.
+    pub fn full_name(&self) -> &str {
+        match self.full_name {
+            Some(ref full_name) => full_name,
+            None => self.name,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -47,7 +68,7 @@ mod tests {
     fn label_fmt() {
         fn subject(label: ExampleLabel) -> String {
             format!("{}", label)
-        };
+        }
         assert_eq!(subject(ExampleLabel::Example), "Example".to_owned());
         assert_eq!(subject(ExampleLabel::It), "It".to_owned());
         assert_eq!(subject(ExampleLabel::Then), "Then".to_owned());
@@ -57,7 +78,7 @@ mod tests {
     fn header_fmt() {
         fn subject(label: ExampleLabel) -> String {
             format!("{}", ExampleHeader::new(label, "Test"))
-        };
+        }
         assert_eq!(
             subject(ExampleLabel::Example),
             "Example \"Test\"".to_owned()
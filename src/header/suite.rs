@@ -1,4 +1,5 @@
 use std::fmt;
+use std::panic::Location;
 
 /// How the [`Suite`](../block/struct.Suite.html) will be printed by the [`Logger`](../logger/index.html).
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -23,6 +24,9 @@ impl fmt::Display for SuiteLabel {
 pub struct SuiteHeader {
     pub label: SuiteLabel,
     pub name: &'static str,
+    /// Where the block got declared, if known.
+    #[new(default)]
+    pub location: Option<&'static Location<'static>>,
 }
 
 impl fmt::Display for SuiteHeader {
@@ -39,7 +43,7 @@ mod tests {
     fn label_fmt() {
         fn subject(label: SuiteLabel) -> String {
             format!("{}", label)
-        };
+        }
         assert_eq!(subject(SuiteLabel::Suite), "Suite".to_owned());
         assert_eq!(subject(SuiteLabel::Describe), "Describe".to_owned());
         assert_eq!(subject(SuiteLabel::Given), "Given".to_owned());
@@ -49,7 +53,7 @@ mod tests {
     fn header_fmt() {
         fn subject(label: SuiteLabel) -> String {
             format!("{}", SuiteHeader::new(label, "Test"))
-        };
+        }
         assert_eq!(subject(SuiteLabel::Suite), "Suite \"Test\"".to_owned());
         assert_eq!(
             subject(SuiteLabel::Describe),
@@ -2,6 +2,7 @@
 #![cfg_attr(feature = "clippy", feature(plugin))]
 #![cfg_attr(feature = "clippy", plugin(clippy))]
 #![allow(dead_code)]
+#![cfg_attr(test, allow(clippy::bool_assert_comparison))]
 
 #[macro_use]
 extern crate derive_builder;
@@ -12,57 +13,86 @@ extern crate derive_new;
 extern crate colored;
 #[cfg(feature = "expectest_compat")]
 extern crate expectest;
+#[doc(hidden)]
+pub extern crate inventory;
 extern crate rayon;
+extern crate regex;
+extern crate rspec_macros;
 extern crate time;
 
+#[macro_use]
+mod macros;
+
 pub mod block;
+pub mod cli;
 pub mod header;
 pub mod logger;
+pub mod registry;
 pub mod report;
 pub mod runner;
 
 mod visitor;
 
-pub use block::{describe, given, suite};
+pub use block::{describe, describe_with, given, given_with, suite, suite_with};
 pub use logger::Logger;
-pub use runner::{Configuration, ConfigurationBuilder, Runner};
+pub use runner::{Configuration, ConfigurationBuilder, Filter, PendingMode, RunSummary, Runner};
+
+#[doc(hidden)]
+pub use rspec_macros::rspec as __rspec;
 
 use block::Suite;
 
 /// A wrapper for conveniently running a test suite with
 /// the default configuration with considerebly less glue-code.
 ///
+/// Blocks get executed in random order if the `RSPEC_SEED` environment variable
+/// provides a seed (see [`SEED_ENV_VAR`](runner/constant.SEED_ENV_VAR.html)).
+///
+/// Returns the run's outcome, for the test binary to exit with
+/// (see [`RunSummary`](struct.RunSummary.html)).
+///
+/// Examples run on threads of their own, so that they get given up on once they exceed
+/// their timeout (see [`Context::timeout`](block/struct.Context.html#method.timeout)),
+/// which requires a `'static` environment. Suites borrowing their environment
+/// can be run with [`Runner::run`](runner/struct.Runner.html#method.run) instead.
+///
 /// # Examples
 ///
 /// ```
 /// # extern crate rspec;
 /// #
-/// # pub fn main() {
-/// rspec::run(&rspec::given("a scenario", (), |ctx| {
-///     ctx.when("...", |ctx| {
-///         // ...
-///     });
+/// pub fn main() -> rspec::RunSummary {
+///     rspec::run(&rspec::given("a scenario", (), |ctx| {
+///         ctx.when("...", |ctx| {
+///             // ...
+///         });
 ///
-///     ctx.then("...", |env| { /* ... */ });
-/// }));
-/// # }
+///         ctx.then("...", |env| { /* ... */ });
+///     }))
+/// }
 /// ```
-pub fn run<T>(suite: &Suite<T>)
+pub fn run<T>(suite: &Suite<T>) -> RunSummary
 where
-    T: Clone + Send + Sync + ::std::fmt::Debug,
+    T: 'static + Send + Sync + ::std::fmt::Debug,
 {
     use std::io;
     use std::sync::Arc;
 
     let logger = Arc::new(Logger::new(io::stdout()));
-    let configuration = ConfigurationBuilder::default().build().unwrap();
+    let mut builder = ConfigurationBuilder::default();
+    if let Some(seed) = runner::seed_from_env() {
+        builder.seed(seed);
+    }
+    let configuration = builder.build().unwrap();
     let runner = Runner::new(configuration, vec![logger]);
 
-    runner.run(suite);
+    runner.run_threaded(suite);
+    runner.finish()
 }
 
 #[cfg(test)]
 mod tests {
+    #![allow(unused_imports)]
 
     pub use super::*;
     pub use block::*;
@@ -78,14 +108,14 @@ mod tests {
     // x runner broadcasts run events
     // x progress logger is an event handler
     // x pluggable loggers via logger trait
-    // - stats time events is an event handler
-    // - detect slow tests via treshold
+    // x stats time events is an event handler
+    // x detect slow tests via treshold
     // x time the total running time
     // - failure-only via a tmp file
-    // - filter tests
+    // x filter tests
     // - coloration
-    // - seed for deterministic randomization
-    // - fail-fast fail at the first failed test
+    // x seed for deterministic randomization
+    // x fail-fast fail at the first failed test
     // x beforeAll
     // x afterAll
     // x beforeEach
@@ -0,0 +1,88 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use time::Duration;
+
+use report::{Report, RunReport, SuiteReport};
+
+/// The reports of all suites run by a runner, be they run one by one
+/// (see [`Runner::run`](../runner/struct.Runner.html#method.run)) or together
+/// (see [`Runner::run_all`](../runner/struct.Runner.html#method.run_all)),
+/// for loggers writing a single document for all of them.
+pub(crate) struct Collected {
+    in_run: AtomicBool,
+    reports: Mutex<(Vec<SuiteReport>, Duration)>,
+}
+
+impl Collected {
+    pub(crate) fn new() -> Self {
+        Collected {
+            in_run: AtomicBool::new(false),
+            reports: Mutex::new((vec![], Duration::zero())),
+        }
+    }
+
+    pub(crate) fn enter_run(&self) {
+        self.in_run.store(true, Ordering::SeqCst);
+    }
+
+    pub(crate) fn exit_run(&self, report: &RunReport) {
+        self.in_run.store(false, Ordering::SeqCst);
+        if let Ok(mut reports) = self.reports.lock() {
+            reports.1 += report.get_duration();
+        }
+    }
+
+    pub(crate) fn exit_suite(&self, report: &SuiteReport) {
+        // Suites run together take as long as their run, rather than the sum of their durations:
+        let is_in_run = self.in_run.load(Ordering::SeqCst);
+        if let Ok(mut reports) = self.reports.lock() {
+            reports.0.push(report.clone());
+            if !is_in_run {
+                reports.1 += report.get_duration();
+            }
+        }
+    }
+
+    /// Returns the suites collected so far as a single run, if any.
+    pub(crate) fn take(&self) -> Option<RunReport> {
+        let mut reports = self.reports.lock().ok()?;
+        let (suites, duration) = ::std::mem::replace(&mut *reports, (vec![], Duration::zero()));
+        if suites.is_empty() {
+            None
+        } else {
+            Some(RunReport::new(suites, duration))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use header::{SuiteHeader, SuiteLabel};
+    use report::ContextReport;
+
+    fn suite(name: &'static str, milliseconds: i64) -> SuiteReport {
+        let context = ContextReport::new(vec![], Duration::milliseconds(milliseconds));
+        SuiteReport::new(SuiteHeader::new(SuiteLabel::Suite, name), context, false)
+    }
+
+    #[test]
+    fn it_collects_suites_across_runs() {
+        // arrange
+        let collected = Collected::new();
+        let (first, second, third) = (suite("a", 10), suite("b", 20), suite("c", 30));
+        // act
+        collected.exit_suite(&first);
+        collected.enter_run();
+        collected.exit_suite(&second);
+        collected.exit_suite(&third);
+        collected.exit_run(&RunReport::new(vec![], Duration::milliseconds(35)));
+        // assert
+        let report = collected.take().unwrap();
+        assert_eq!(report.get_suites(), &[first, second, third]);
+        assert_eq!(report.get_duration(), Duration::milliseconds(45));
+        assert!(collected.take().is_none());
+    }
+}
@@ -0,0 +1,38 @@
+//! Fixtures shared by the loggers' tests.
+
+use std::io;
+use std::sync::{Arc, Mutex};
+
+use time::Duration;
+
+use header::{ExampleHeader, ExampleLabel};
+use report::{BlockReport, ExampleReport, ExampleResult};
+
+/// A buffer that can still be read once the logger writing to it got dropped.
+#[derive(Clone, Default)]
+pub(crate) struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    /// Returns what got written so far.
+    pub(crate) fn contents(&self) -> String {
+        String::from_utf8_lossy(&self.0.lock().unwrap()).into_owned()
+    }
+}
+
+impl io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Returns the report of an example named `name`, which took `milliseconds`.
+pub(crate) fn example(name: &'static str, result: ExampleResult, milliseconds: i64) -> BlockReport {
+    BlockReport::Example(
+        ExampleHeader::new(ExampleLabel::It, name),
+        ExampleReport::new(result, Duration::milliseconds(milliseconds)),
+    )
+}
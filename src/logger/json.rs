@@ -0,0 +1,545 @@
+use std::io;
+use std::panic::Location;
+use std::sync::Mutex;
+
+use time::Duration;
+
+use header::{ContextHeader, ExampleHeader, SuiteHeader};
+use logger;
+use logger::collected::Collected;
+use report::{
+    BlockReport, ContextReport, ExampleReport, ExampleResult, Report, RunReport, SuiteReport,
+};
+use runner::{Runner, RunnerObserver};
+
+/// Logger writing a test suite's results as JSON, for consumption by other tools.
+///
+/// By default the logger writes the whole report trees of all suites run as a single JSON document
+/// once it gets dropped (see [`JsonLogger::new`](#method.new)). In streaming mode it instead writes
+/// one JSON object per line for each event as it happens (see [`JsonLogger::streaming`](#method.streaming)).
+///
+/// # Examples
+///
+/// ```
+/// # extern crate rspec;
+/// #
+/// # use std::io;
+/// # use std::sync::Arc;
+/// #
+/// # pub fn main() {
+/// let logger = Arc::new(rspec::logger::JsonLogger::new(io::stdout()));
+/// let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+/// let runner = rspec::Runner::new(configuration, vec![logger]);
+/// # }
+/// ```
+pub struct JsonLogger<T: io::Write = io::Stdout> {
+    buffer: Mutex<T>,
+    streaming: bool,
+    collected: Collected,
+    /// The seed of the runner the suites got collected from (see `Runner::seed`).
+    seed: Mutex<Option<u64>>,
+}
+
+impl<T: io::Write> JsonLogger<T> {
+    /// Creates a logger writing the whole report trees of all suites run, be they run
+    /// one by one or together, as a single document upon being dropped
+    /// (which the runner owning it does before exiting the process).
+    ///
+    /// ```text
+    /// {"success":true,"passed":1,…,"suites":[{"label":"Suite","name":"a suite",…,"context":{"blocks":[…]}}]}
+    /// ```
+    pub fn new(buffer: T) -> Self {
+        Self::with_streaming(buffer, false)
+    }
+
+    /// Creates a logger writing newline-delimited JSON, one line per runner event.
+    ///
+    /// Events of a suite being executed in parallel
+    /// (see [`Configuration.parallel`](../struct.Configuration.html#structfield.parallel))
+    /// get written in the order they happen, rather than in the order of their blocks.
+    ///
+    /// Suites run together (see [`Runner::run_all`](../struct.Runner.html#method.run_all))
+    /// are enclosed by `enter_run` and `exit_run` events, the latter holding the run's summary.
+    ///
+    /// ```text
+    /// {"event":"enter_suite","label":"Suite","name":"a suite"}
+    /// {"event":"enter_example","label":"It","name":"an example"}
+    /// {"event":"exit_example","label":"It","name":"an example","result":"success",…}
+    /// {"event":"exit_suite","label":"Suite","name":"a suite","passed":1,…}
+    /// ```
+    pub fn streaming(buffer: T) -> Self {
+        Self::with_streaming(buffer, true)
+    }
+
+    fn with_streaming(buffer: T, streaming: bool) -> Self {
+        JsonLogger {
+            buffer: Mutex::new(buffer),
+            streaming,
+            collected: Collected::new(),
+            seed: Mutex::new(None),
+        }
+    }
+
+    fn write_line(&self, line: &str) {
+        logger::access(&self.buffer, |buffer| {
+            writeln!(buffer, "{}", line).and_then(|_| buffer.flush())
+        });
+    }
+
+    fn event(name: &str) -> Object {
+        let mut object = Object::new();
+        object.field("event", &string(name));
+        object
+    }
+}
+
+impl<T: io::Write> RunnerObserver for JsonLogger<T>
+where
+    T: Send + Sync,
+{
+    fn enter_run(&self, _runner: &Runner) {
+        if self.streaming {
+            self.write_line(&Self::event("enter_run").finish());
+        } else {
+            self.collected.enter_run();
+        }
+    }
+
+    fn exit_run(&self, runner: &Runner, report: &RunReport) {
+        if self.streaming {
+            let mut event = Self::event("exit_run");
+            write_run_summary(&mut event, runner.seed(), report);
+            self.write_line(&event.finish());
+        } else {
+            self.collected.exit_run(report);
+        }
+    }
+
+    fn enter_suite(&self, _runner: &Runner, header: &SuiteHeader) {
+        if self.streaming {
+            let mut event = Self::event("enter_suite");
+            write_suite_header(&mut event, header);
+            self.write_line(&event.finish());
+        }
+    }
+
+    fn exit_suite(&self, runner: &Runner, header: &SuiteHeader, report: &SuiteReport) {
+        if self.streaming {
+            let mut event = Self::event("exit_suite");
+            write_suite_header(&mut event, header);
+            write_suite_summary(&mut event, runner.seed(), report);
+            self.write_line(&event.finish());
+        } else {
+            if let Ok(mut seed) = self.seed.lock() {
+                *seed = runner.seed();
+            }
+            self.collected.exit_suite(report);
+        }
+    }
+
+    fn enter_context(&self, _runner: &Runner, header: &ContextHeader) {
+        if self.streaming {
+            let mut event = Self::event("enter_context");
+            write_context_header(&mut event, Some(header));
+            self.write_line(&event.finish());
+        }
+    }
+
+    fn exit_context(&self, _runner: &Runner, header: &ContextHeader, report: &ContextReport) {
+        if self.streaming {
+            let mut event = Self::event("exit_context");
+            write_context_header(&mut event, Some(header));
+            write_summary(&mut event, report);
+            event.field("hook_failures", &hook_failures(report));
+            self.write_line(&event.finish());
+        }
+    }
+
+    fn enter_example(&self, _runner: &Runner, header: &ExampleHeader) {
+        if self.streaming {
+            let mut event = Self::event("enter_example");
+            write_example_header(&mut event, header);
+            self.write_line(&event.finish());
+        }
+    }
+
+    fn exit_example(&self, _runner: &Runner, header: &ExampleHeader, report: &ExampleReport) {
+        if self.streaming {
+            let mut event = Self::event("exit_example");
+            write_example_header(&mut event, header);
+            write_example_report(&mut event, report);
+            self.write_line(&event.finish());
+        }
+    }
+}
+
+impl<T: io::Write> Drop for JsonLogger<T> {
+    fn drop(&mut self) {
+        // A document has a single root value, so all suites get written at once:
+        if let Some(report) = self.collected.take() {
+            let seed = self.seed.lock().ok().and_then(|seed| *seed);
+            self.write_line(&run(seed, &report));
+        }
+    }
+}
+
+/// A JSON object being written, field by field.
+pub(crate) struct Object {
+    json: String,
+}
+
+impl Object {
+    pub fn new() -> Self {
+        Object {
+            json: "{".to_owned(),
+        }
+    }
+
+    /// Appends a field, whose `value` is expected to be valid JSON already.
+    pub fn field(&mut self, key: &str, value: &str) -> &mut Self {
+        if self.json.len() > 1 {
+            self.json.push(',');
+        }
+        self.json.push_str(&string(key));
+        self.json.push(':');
+        self.json.push_str(value);
+        self
+    }
+
+    pub fn finish(mut self) -> String {
+        self.json.push('}');
+        self.json
+    }
+}
+
+/// Returns `value` as a JSON string literal.
+pub(crate) fn string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for character in value.chars() {
+        match character {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Returns `value` as a JSON string literal, or `null`.
+pub(crate) fn optional_string(value: Option<&str>) -> String {
+    value.map_or_else(|| "null".to_owned(), string)
+}
+
+/// Returns `elements` (each being valid JSON already) as a JSON array.
+pub(crate) fn array(elements: &[String]) -> String {
+    format!("[{}]", elements.join(","))
+}
+
+/// Returns `duration` in (fractional) seconds.
+pub(crate) fn seconds(duration: Duration) -> String {
+    format!("{}", duration.as_seconds_f64())
+}
+
+fn run(seed: Option<u64>, report: &RunReport) -> String {
+    let mut object = Object::new();
+    write_run_summary(&mut object, seed, report);
+    let suites: Vec<_> = report
+        .get_suites()
+        .iter()
+        .map(|report| suite(seed, report))
+        .collect();
+    object.field("suites", &array(&suites));
+    object.finish()
+}
+
+fn suite(seed: Option<u64>, report: &SuiteReport) -> String {
+    let mut object = Object::new();
+    write_suite_header(&mut object, report.get_header());
+    write_suite_summary(&mut object, seed, report);
+    object.field("context", &context(None, report.get_context()));
+    object.finish()
+}
+
+fn context(header: Option<&ContextHeader>, report: &ContextReport) -> String {
+    let mut object = Object::new();
+    object.field("type", &string("context"));
+    write_context_header(&mut object, header);
+    write_summary(&mut object, report);
+    object.field("hook_failures", &hook_failures(report));
+    let blocks: Vec<_> = report.get_blocks().iter().map(block).collect();
+    object.field("blocks", &array(&blocks));
+    object.finish()
+}
+
+fn hook_failures(report: &ContextReport) -> String {
+    let failures: Vec<_> = report
+        .get_hook_failures()
+        .iter()
+        .map(|failure| {
+            let mut object = Object::new();
+            object
+                .field("hook", &string(&failure.get_kind().to_string()))
+                .field("message", &optional_string(failure.get_message()));
+            object.finish()
+        })
+        .collect();
+    array(&failures)
+}
+
+fn block(report: &BlockReport) -> String {
+    match report {
+        BlockReport::Context(ref header, ref report) => context(header.as_ref(), report),
+        BlockReport::Example(ref header, ref report) => {
+            let mut object = Object::new();
+            object.field("type", &string("example"));
+            write_example_header(&mut object, header);
+            write_example_report(&mut object, report);
+            object.finish()
+        }
+    }
+}
+
+fn write_suite_header(object: &mut Object, header: &SuiteHeader) {
+    object
+        .field("label", &string(&header.label.to_string()))
+        .field("name", &string(header.name))
+        .field("location", &location(header.location));
+}
+
+fn write_context_header(object: &mut Object, header: Option<&ContextHeader>) {
+    let label = header.map(|header| header.label.to_string());
+    object
+        .field("label", &optional_string(label.as_ref().map(|s| &s[..])))
+        .field("name", &optional_string(header.map(|header| header.name)))
+        .field(
+            "location",
+            &location(header.and_then(|header| header.location)),
+        );
+}
+
+fn write_example_header(object: &mut Object, header: &ExampleHeader) {
+    object
+        .field("label", &string(&header.label.to_string()))
+        .field("name", &string(header.name))
+        .field("location", &location(header.location));
+}
+
+/// Returns `location` as a JSON object with `file`, `line` and `column`, or `null`.
+pub(crate) fn location(location: Option<&Location>) -> String {
+    location.map_or_else(
+        || "null".to_owned(),
+        |location| {
+            let mut object = Object::new();
+            object
+                .field("file", &string(location.file()))
+                .field("line", &location.line().to_string())
+                .field("column", &location.column().to_string());
+            object.finish()
+        },
+    )
+}
+
+fn write_run_summary(object: &mut Object, seed: Option<u64>, report: &RunReport) {
+    write_summary(object, report);
+    write_seed(object, report.is_focused(), seed);
+}
+
+fn write_suite_summary(object: &mut Object, seed: Option<u64>, report: &SuiteReport) {
+    write_summary(object, report);
+    write_seed(object, report.is_focused(), seed);
+}
+
+fn write_seed(object: &mut Object, is_focused: bool, seed: Option<u64>) {
+    let seed = seed.map(|seed| seed.to_string());
+    object
+        .field("focused", &is_focused.to_string())
+        .field("seed", seed.as_ref().map_or("null", |s| &s[..]));
+}
+
+fn write_summary<R: Report>(object: &mut Object, report: &R) {
+    object
+        .field("success", &report.is_success().to_string())
+        .field("passed", &report.get_passed().to_string())
+        .field("failed", &report.get_failed().to_string())
+        .field("flaky", &report.get_flaky().to_string())
+        .field("ignored", &report.get_ignored().to_string())
+        .field("pending", &report.get_pending().to_string())
+        .field("filtered", &report.get_filtered().to_string())
+        .field("not_run", &report.get_not_run().to_string())
+        .field("duration", &seconds(report.get_duration()));
+}
+
+fn write_example_report(object: &mut Object, report: &ExampleReport) {
+    write_result(object, report.get_result());
+    object.field("duration", &seconds(report.get_duration()));
+    let attempts: Vec<_> = report
+        .get_attempts()
+        .iter()
+        .map(|attempt| {
+            let mut object = Object::new();
+            write_result(&mut object, attempt.get_result());
+            object.field("duration", &seconds(attempt.get_duration()));
+            object.finish()
+        })
+        .collect();
+    object.field("attempts", &array(&attempts));
+}
+
+fn write_result(object: &mut Object, result: &ExampleResult) {
+    let (name, message) = match result {
+        ExampleResult::Success => ("success", None),
+        ExampleResult::Failure(ref message) => ("failure", message.as_ref()),
+        ExampleResult::TimedOut(_) => ("timed_out", None),
+        ExampleResult::Ignored => ("ignored", None),
+        ExampleResult::Pending(ref reason) => ("pending", reason.as_ref()),
+        ExampleResult::Filtered => ("filtered", None),
+        ExampleResult::NotRun => ("not_run", None),
+    };
+    object
+        .field("result", &string(name))
+        .field("message", &optional_string(message.map(|s| &s[..])));
+    if let ExampleResult::TimedOut(timeout) = result {
+        object.field("timeout", &seconds(*timeout));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use block::suite;
+    use logger::fixtures::SharedBuffer;
+    use runner::ConfigurationBuilder;
+
+    fn lines(buffer: &SharedBuffer) -> Vec<String> {
+        buffer
+            .contents()
+            .lines()
+            .map(|line| line.to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn it_escapes_strings() {
+        assert_eq!(string("a \"b\"\n\\c\u{1}"), r#""a \"b\"\n\\c\u0001""#);
+    }
+
+    #[test]
+    fn it_writes_objects() {
+        let mut object = Object::new();
+        object.field("a", "1").field("b", &string("c"));
+        assert_eq!(object.finish(), r#"{"a":1,"b":"c"}"#);
+        assert_eq!(Object::new().finish(), "{}");
+    }
+
+    fn run(logger: JsonLogger<SharedBuffer>) {
+        let config = ConfigurationBuilder::default()
+            .parallel(false)
+            .exit_on_failure(false)
+            .build()
+            .unwrap();
+        let runner = Runner::new(config, vec![Arc::new(logger)]);
+        runner.run(&suite("suite", (), |ctx| {
+            ctx.context("context", |ctx| {
+                ctx.it("passes", |_| true);
+                ctx.it("fails", |_| Err("reason") as Result<(), _>);
+            });
+        }));
+    }
+
+    #[test]
+    fn it_writes_the_report_tree() {
+        // arrange
+        let buffer = SharedBuffer::default();
+        // act
+        run(JsonLogger::new(buffer.clone()));
+        // assert
+        let lines = lines(&buffer);
+        assert_eq!(lines.len(), 1);
+        let json = &lines[0];
+        assert!(json.starts_with(r#"{"success":false,"passed":1,"failed":1,"#));
+        assert!(json.contains(
+            r#","suites":[{"label":"Suite","name":"suite","location":{"file":"src/logger/json.rs","line":"#
+        ));
+        assert!(json.contains(r#"},"success":false,"passed":1,"failed":1,"#));
+        assert!(json.contains(
+            r#""seed":null,"context":{"type":"context","label":null,"name":null,"location":null,"#
+        ));
+        assert!(json.contains(
+            r#""blocks":[{"type":"context","label":"Context","name":"context","location":{"#
+        ));
+        assert!(json.contains(r#""hook_failures":[],"blocks":["#));
+        assert!(json.contains(r#"{"type":"example","label":"It","name":"passes","location":{"#));
+        assert!(json.contains(r#"},"result":"success","message":null,"#));
+        assert!(json.contains(r#"},"result":"failure","message":"\"reason\"","#));
+    }
+
+    #[test]
+    fn it_writes_a_single_document_for_all_suites() {
+        // arrange
+        let buffer = SharedBuffer::default();
+        let config = ConfigurationBuilder::default()
+            .parallel(false)
+            .seed(42)
+            .build()
+            .unwrap();
+        let runner = Runner::new(config, vec![Arc::new(JsonLogger::new(buffer.clone()))]);
+        let first = suite("first", (), |ctx| {
+            ctx.it("passes", |_| {});
+        });
+        let second = suite("second", (), |ctx| {
+            ctx.it("passes", |_| {});
+        });
+        // act
+        runner.run_all(&[&first, &second]);
+        runner.run(&first);
+        assert!(buffer.contents().is_empty());
+        drop(runner);
+        // assert
+        let lines = lines(&buffer);
+        assert_eq!(lines.len(), 1);
+        let json = &lines[0];
+        assert!(json.starts_with(r#"{"success":true,"passed":3,"failed":0,"#));
+        assert!(json
+            .contains(r#""focused":false,"seed":42,"suites":[{"label":"Suite","name":"first","#));
+        assert!(json.contains(r#"},{"label":"Suite","name":"second","#));
+        assert_eq!(json.matches(r#"{"label":"Suite","#).count(), 3);
+        assert!(json.ends_with("}]}"));
+    }
+
+    #[test]
+    fn it_streams_events() {
+        // arrange
+        let buffer = SharedBuffer::default();
+        // act
+        run(JsonLogger::streaming(buffer.clone()));
+        // assert
+        let events: Vec<_> = lines(&buffer)
+            .iter()
+            .map(|line| line.split('"').nth(3).unwrap().to_owned())
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                "enter_suite",
+                "enter_context",
+                "enter_example",
+                "exit_example",
+                "enter_example",
+                "exit_example",
+                "exit_context",
+                "exit_suite",
+            ]
+        );
+        assert!(lines(&buffer)[3].contains(r#""name":"passes","location":{"#));
+        assert!(lines(&buffer)[3].contains(r#"},"result":"success","#));
+    }
+}
@@ -0,0 +1,398 @@
+use std::io;
+use std::sync::Mutex;
+
+use time::Duration;
+
+use header::{ContextHeader, ExampleHeader, SuiteHeader};
+use logger;
+use logger::collected::Collected;
+use report::{
+    BlockReport, ContextReport, ExampleReport, ExampleResult, Report, RunReport, SuiteReport,
+};
+use runner::{Runner, RunnerObserver};
+
+/// Logger writing a test suite's results as JUnit XML, as ingested by most CI systems.
+///
+/// Each suite gets written as a `<testsuite>`, with a `<testcase>` per example.
+/// All suites run by the runner (be they run one by one or together, see [`Runner::run_all`](../struct.Runner.html#method.run_all))
+/// get written as a single document once the logger gets dropped, usually along with the runner.
+/// An example's `classname` is the path of its suite and enclosing contexts (e.g. `"suite > context"`),
+/// while its `file` and `line` are where it got declared.
+/// Failed hooks get written as failed `<testcase>`s of their own (e.g. `"after_all hook"`),
+/// while filtered out examples are omitted, as they are not part of the run.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate rspec;
+/// #
+/// # use std::fs::File;
+/// # use std::sync::Arc;
+/// #
+/// # pub fn main() {
+/// # let path = std::env::temp_dir().join("rspec-junit-doctest.xml");
+/// let file = File::create(path).unwrap();
+/// let logger = Arc::new(rspec::logger::JUnitLogger::new(file));
+/// let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+/// let runner = rspec::Runner::new(configuration, vec![logger]);
+/// # }
+/// ```
+pub struct JUnitLogger<T: io::Write = io::Stdout> {
+    buffer: Mutex<T>,
+    collected: Collected,
+}
+
+impl<T: io::Write> JUnitLogger<T> {
+    pub fn new(buffer: T) -> Self {
+        JUnitLogger {
+            buffer: Mutex::new(buffer),
+            collected: Collected::new(),
+        }
+    }
+
+    fn write(&self, xml: &str) {
+        logger::access(&self.buffer, |buffer| {
+            buffer
+                .write_all(xml.as_bytes())
+                .and_then(|_| buffer.flush())
+        });
+    }
+}
+
+impl<T: io::Write> RunnerObserver for JUnitLogger<T>
+where
+    T: Send + Sync,
+{
+    fn enter_run(&self, _runner: &Runner) {
+        self.collected.enter_run();
+    }
+
+    fn exit_run(&self, _runner: &Runner, report: &RunReport) {
+        self.collected.exit_run(report);
+    }
+
+    fn exit_suite(&self, _runner: &Runner, _header: &SuiteHeader, report: &SuiteReport) {
+        self.collected.exit_suite(report);
+    }
+}
+
+impl<T: io::Write> Drop for JUnitLogger<T> {
+    fn drop(&mut self) {
+        // A document has a single root element, so all suites get written at once:
+        if let Some(report) = self.collected.take() {
+            self.write(&document(&report, report.get_suites()));
+        }
+    }
+}
+
+/// Returns `value` escaped for use in XML attributes and text.
+///
+/// Control characters aren't allowed in XML 1.0 documents and get replaced.
+pub(crate) fn escape(value: &str) -> String {
+    let mut xml = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '&' => xml.push_str("&amp;"),
+            '<' => xml.push_str("&lt;"),
+            '>' => xml.push_str("&gt;"),
+            '"' => xml.push_str("&quot;"),
+            '\'' => xml.push_str("&apos;"),
+            '\n' => xml.push_str("&#10;"),
+            '\r' => xml.push_str("&#13;"),
+            '\t' => xml.push_str("&#9;"),
+            c if (c as u32) < 0x20 => xml.push('\u{fffd}'),
+            c => xml.push(c),
+        }
+    }
+    xml
+}
+
+fn seconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_seconds_f64())
+}
+
+/// Returns the number of examples that end up as `<testcase>`s.
+fn num_test_cases<R: Report>(report: &R) -> u32 {
+    report.get_passed()
+        + report.get_failed()
+        + report.get_ignored()
+        + report.get_pending()
+        + report.get_not_run()
+}
+
+/// Returns the number of failed hooks, which end up as failed `<testcase>`s.
+fn num_hook_failures(suites: &[SuiteReport]) -> u32 {
+    suites
+        .iter()
+        .map(|suite| suite.get_context().num_hook_failures())
+        .sum()
+}
+
+fn document<R: Report>(report: &R, suites: &[SuiteReport]) -> String {
+    let mut xml = String::new();
+    let hook_failures = num_hook_failures(suites);
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" time=\"{}\">\n",
+        num_test_cases(report) + hook_failures,
+        report.get_failed() + hook_failures,
+        num_test_cases(report) - report.get_passed() - report.get_failed(),
+        seconds(report.get_duration()),
+    ));
+    for suite in suites {
+        write_suite(&mut xml, suite);
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+fn write_suite(xml: &mut String, report: &SuiteReport) {
+    let header = report.get_header();
+    let hook_failures = report.get_context().num_hook_failures();
+    xml.push_str(&format!(
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" time=\"{}\">\n",
+        escape(header.name),
+        num_test_cases(report) + hook_failures,
+        report.get_failed() + hook_failures,
+        num_test_cases(report) - report.get_passed() - report.get_failed(),
+        seconds(report.get_duration()),
+    ));
+    write_context(xml, header.name, report.get_context());
+    xml.push_str("  </testsuite>\n");
+}
+
+fn write_context(xml: &mut String, classname: &str, report: &ContextReport) {
+    for hook_failure in report.get_hook_failures() {
+        xml.push_str(&format!(
+            "    <testcase name=\"{} hook\" classname=\"{}\" time=\"0.000\">\n",
+            hook_failure.get_kind(),
+            escape(classname),
+        ));
+        xml.push_str(&failure("failure", hook_failure.get_message()));
+        xml.push_str("    </testcase>\n");
+    }
+    for block in report.get_blocks() {
+        match block {
+            BlockReport::Context(ref header, ref report) => {
+                let classname = context_classname(classname, header.as_ref());
+                write_context(xml, &classname, report);
+            }
+            BlockReport::Example(ref header, ref report) => {
+                write_example(xml, classname, header, report);
+            }
+        }
+    }
+}
+
+fn context_classname(parent: &str, header: Option<&ContextHeader>) -> String {
+    match header {
+        Some(header) => format!("{} > {}", parent, header.name),
+        None => parent.to_owned(),
+    }
+}
+
+fn write_example(
+    xml: &mut String,
+    classname: &str,
+    header: &ExampleHeader,
+    report: &ExampleReport,
+) {
+    if let ExampleResult::Filtered = report.get_result() {
+        return;
+    }
+    xml.push_str(&format!(
+        "    <testcase name=\"{}\" classname=\"{}\" time=\"{}\"",
+        escape(header.name),
+        escape(classname),
+        seconds(report.get_duration()),
+    ));
+    if let Some(location) = header.location {
+        xml.push_str(&format!(
+            " file=\"{}\" line=\"{}\"",
+            escape(location.file()),
+            location.line()
+        ));
+    }
+    let mut children = String::new();
+    match report.get_result() {
+        ExampleResult::Success => {}
+        ExampleResult::Failure(ref message) => {
+            children.push_str(&failure("failure", message.as_ref().map(|s| &s[..])));
+        }
+        ExampleResult::TimedOut(timeout) => {
+            let message = format!("timed out after {}s", seconds(*timeout));
+            children.push_str(&failure("failure", Some(&message)));
+        }
+        ExampleResult::Ignored => children.push_str("      <skipped/>\n"),
+        ExampleResult::Pending(ref reason) => {
+            let message = reason.as_ref().map_or("pending", |s| &s[..]);
+            children.push_str(&format!(
+                "      <skipped message=\"{}\"/>\n",
+                escape(message)
+            ));
+        }
+        ExampleResult::NotRun => children.push_str("      <skipped message=\"not run\"/>\n"),
+        ExampleResult::Filtered => unreachable!(),
+    }
+    // Earlier failed attempts of retried examples, using the Maven Surefire extension elements:
+    let attempts = report.get_attempts();
+    let element = if report.is_flaky() {
+        "flakyFailure"
+    } else {
+        "rerunFailure"
+    };
+    for attempt in attempts.iter().take(attempts.len().saturating_sub(1)) {
+        match attempt.get_result() {
+            ExampleResult::Failure(ref message) => {
+                children.push_str(&failure(element, message.as_ref().map(|s| &s[..])));
+            }
+            ExampleResult::TimedOut(timeout) => {
+                let message = format!("timed out after {}s", seconds(*timeout));
+                children.push_str(&failure(element, Some(&message)));
+            }
+            _ => {}
+        }
+    }
+    if children.is_empty() {
+        xml.push_str("/>\n");
+    } else {
+        xml.push_str(">\n");
+        xml.push_str(&children);
+        xml.push_str("    </testcase>\n");
+    }
+}
+
+fn failure(element: &str, message: Option<&str>) -> String {
+    match message {
+        Some(message) => format!("      <{} message=\"{}\"/>\n", element, escape(message)),
+        None => format!("      <{}/>\n", element),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use block::suite;
+    use header::*;
+    use logger::fixtures::{example, SharedBuffer};
+    use report::*;
+    use runner::ConfigurationBuilder;
+
+    #[test]
+    fn it_escapes_strings() {
+        assert_eq!(
+            escape("<a href=\"b\">&'\n\u{1}"),
+            "&lt;a href=&quot;b&quot;&gt;&amp;&apos;&#10;\u{fffd}"
+        );
+    }
+
+    #[test]
+    fn it_writes_the_suite() {
+        // arrange
+        let context = ContextReport::new(
+            vec![
+                example(
+                    "fails",
+                    ExampleResult::Failure(Some("a < b".to_owned())),
+                    1500,
+                ),
+                example("is ignored", ExampleResult::Ignored, 1500),
+                example("is filtered", ExampleResult::Filtered, 1500),
+            ],
+            Duration::milliseconds(3000),
+        )
+        .with_hook_failures(vec![HookFailure::new(
+            HookKind::AfterAll,
+            Some("boom".to_owned()),
+        )]);
+        let root = ContextReport::new(
+            vec![
+                example("passes", ExampleResult::Success, 1500),
+                BlockReport::Context(
+                    Some(ContextHeader::new(ContextLabel::When, "context")),
+                    context,
+                ),
+            ],
+            Duration::milliseconds(4500),
+        );
+        let report = SuiteReport::new(SuiteHeader::new(SuiteLabel::Given, "suite"), root, false);
+        // act
+        let xml = document(&report, ::std::slice::from_ref(&report));
+        // assert
+        let expected = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<testsuites tests=\"4\" failures=\"2\" errors=\"0\" skipped=\"1\" time=\"4.500\">
+  <testsuite name=\"suite\" tests=\"4\" failures=\"2\" errors=\"0\" skipped=\"1\" time=\"4.500\">
+    <testcase name=\"passes\" classname=\"suite\" time=\"1.500\"/>
+    <testcase name=\"after_all hook\" classname=\"suite &gt; context\" time=\"0.000\">
+      <failure message=\"boom\"/>
+    </testcase>
+    <testcase name=\"fails\" classname=\"suite &gt; context\" time=\"1.500\">
+      <failure message=\"a &lt; b\"/>
+    </testcase>
+    <testcase name=\"is ignored\" classname=\"suite &gt; context\" time=\"1.500\">
+      <skipped/>
+    </testcase>
+  </testsuite>
+</testsuites>
+";
+        assert_eq!(xml, expected);
+    }
+
+    #[test]
+    fn it_writes_a_single_document_across_runs() {
+        // arrange
+        let buffer = SharedBuffer::default();
+        let config = ConfigurationBuilder::default().build().unwrap();
+        let logger = Arc::new(JUnitLogger::new(buffer.clone()));
+        let runner = Runner::new(config, vec![logger]);
+        // act
+        runner.run(&suite("first", (), |ctx| ctx.it("passes", |_| {})));
+        runner.run(&suite("second", (), |ctx| ctx.it("passes", |_| {})));
+        drop(runner);
+        // assert
+        let xml = buffer.contents();
+        assert_eq!(xml.matches("<?xml").count(), 1);
+        assert!(xml.contains("<testsuites tests=\"2\" failures=\"0\""));
+        assert_eq!(xml.matches("<testsuite ").count(), 2);
+    }
+
+    #[test]
+    fn it_writes_flaky_failures() {
+        // arrange
+        let report = ExampleReport::from_attempts(vec![
+            ExampleAttempt::new(ExampleResult::Failure(None), Duration::zero()),
+            ExampleAttempt::new(ExampleResult::Success, Duration::zero()),
+        ]);
+        let header = ExampleHeader::new(ExampleLabel::It, "is flaky");
+        let mut xml = String::new();
+        // act
+        write_example(&mut xml, "suite", &header, &report);
+        // assert
+        let expected = "    <testcase name=\"is flaky\" classname=\"suite\" time=\"0.000\">
+      <flakyFailure/>
+    </testcase>
+";
+        assert_eq!(xml, expected);
+    }
+
+    #[test]
+    fn it_writes_the_location() {
+        // arrange
+        let report = ExampleReport::new(ExampleResult::Success, Duration::zero());
+        let mut header = ExampleHeader::new(ExampleLabel::It, "passes");
+        let location = ::std::panic::Location::caller();
+        header.location = Some(location);
+        let mut xml = String::new();
+        // act
+        write_example(&mut xml, "suite", &header, &report);
+        // assert
+        let expected = format!(
+            "    <testcase name=\"passes\" classname=\"suite\" time=\"0.000\" file=\"src/logger/junit.rs\" line=\"{}\"/>\n",
+            location.line()
+        );
+        assert_eq!(xml, expected);
+    }
+}
@@ -0,0 +1,292 @@
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use header::{ExampleHeader, SuiteHeader};
+use logger;
+use logger::json::{seconds, string, Object};
+use report::{ExampleReport, ExampleResult, Report, RunReport, SuiteReport};
+use runner::{Runner, RunnerObserver};
+
+/// Logger writing a test suite's results as the JSON event stream of libtest
+/// (the default test harness) when given `--format json`,
+/// as parsed by IDEs and other tools built around `cargo test`.
+///
+/// Events get written as they happen, as one JSON object per line.
+/// Suites run together (see [`Runner::run_all`](../struct.Runner.html#method.run_all))
+/// get written as a single stream.
+/// Test names are the examples' full names, just like in `--list`'s output
+/// (see [`cli`](../cli/index.html)). Pending and ignored examples get reported as ignored,
+/// while not run and filtered out examples are only counted.
+///
+/// ```text
+/// {"type":"suite","event":"started","test_count":2}
+/// {"type":"test","event":"started","name":"a suite > an example"}
+/// {"type":"test","name":"a suite > an example","event":"ok","exec_time":0.001}
+/// {"type":"test","event":"started","name":"a suite > a failing example"}
+/// {"type":"test","name":"a suite > a failing example","event":"failed","exec_time":0.001,"stdout":"…"}
+/// {"type":"suite","event":"failed","passed":1,"failed":1,"ignored":0,"measured":0,"filtered_out":0,"exec_time":0.002}
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// # extern crate rspec;
+/// #
+/// # use std::io;
+/// # use std::sync::Arc;
+/// #
+/// # pub fn main() {
+/// let logger = Arc::new(rspec::logger::LibtestJsonLogger::new(io::stdout()));
+/// let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+/// let runner = rspec::Runner::new(configuration, vec![logger]);
+/// # }
+/// ```
+pub struct LibtestJsonLogger<T: io::Write = io::Stdout> {
+    buffer: Mutex<T>,
+    in_run: AtomicBool,
+}
+
+impl<T: io::Write> LibtestJsonLogger<T> {
+    pub fn new(buffer: T) -> Self {
+        LibtestJsonLogger {
+            buffer: Mutex::new(buffer),
+            in_run: AtomicBool::new(false),
+        }
+    }
+
+    fn write(&self, lines: &[String]) {
+        logger::access(&self.buffer, |buffer| {
+            lines
+                .iter()
+                .try_for_each(|line| writeln!(buffer, "{}", line))
+                .and_then(|_| buffer.flush())
+        });
+    }
+}
+
+impl<T: io::Write> RunnerObserver for LibtestJsonLogger<T>
+where
+    T: Send + Sync,
+{
+    fn enter_run(&self, runner: &Runner) {
+        self.in_run.store(true, Ordering::SeqCst);
+        self.write(&[started(runner.num_selected_examples())]);
+    }
+
+    fn exit_run(&self, _runner: &Runner, report: &RunReport) {
+        self.in_run.store(false, Ordering::SeqCst);
+        self.write(&[finished(report)]);
+    }
+
+    fn enter_suite(&self, runner: &Runner, _header: &SuiteHeader) {
+        if !self.in_run.load(Ordering::SeqCst) {
+            self.write(&[started(runner.num_selected_examples())]);
+        }
+    }
+
+    fn exit_suite(&self, _runner: &Runner, _header: &SuiteHeader, report: &SuiteReport) {
+        if !self.in_run.load(Ordering::SeqCst) {
+            self.write(&[finished(report)]);
+        }
+    }
+
+    fn enter_example(&self, _runner: &Runner, header: &ExampleHeader) {
+        let mut started = Object::new();
+        started
+            .field("type", &string("test"))
+            .field("event", &string("started"))
+            .field("name", &string(header.full_name()));
+        self.write(&[started.finish()]);
+    }
+
+    fn exit_example(&self, _runner: &Runner, header: &ExampleHeader, report: &ExampleReport) {
+        self.write(&[test_event(header, report)]);
+    }
+}
+
+fn started(test_count: usize) -> String {
+    let mut started = Object::new();
+    started
+        .field("type", &string("suite"))
+        .field("event", &string("started"))
+        .field("test_count", &test_count.to_string());
+    started.finish()
+}
+
+fn finished<R: Report>(report: &R) -> String {
+    let ignored = report.get_ignored() + report.get_pending() + report.get_not_run();
+    let event = if report.is_failure() { "failed" } else { "ok" };
+    let mut finished = Object::new();
+    finished
+        .field("type", &string("suite"))
+        .field("event", &string(event))
+        .field("passed", &report.get_passed().to_string())
+        .field("failed", &report.get_failed().to_string())
+        .field("ignored", &ignored.to_string())
+        .field("measured", "0")
+        .field("filtered_out", &report.get_filtered().to_string())
+        .field("exec_time", &seconds(report.get_duration()));
+    finished.finish()
+}
+
+fn test_event(header: &ExampleHeader, report: &ExampleReport) -> String {
+    let mut object = Object::new();
+    object
+        .field("type", &string("test"))
+        .field("name", &string(header.full_name()));
+    match report.get_result() {
+        ExampleResult::Success => {
+            object
+                .field("event", &string("ok"))
+                .field("exec_time", &seconds(report.get_duration()));
+        }
+        ExampleResult::Failure(ref message) => {
+            object
+                .field("event", &string("failed"))
+                .field("exec_time", &seconds(report.get_duration()));
+            if let Some(ref message) = message {
+                object.field("stdout", &string(message));
+            }
+        }
+        ExampleResult::TimedOut(timeout) => {
+            let message = format!("timed out after {}s", timeout.as_seconds_f64());
+            object
+                .field("event", &string("failed"))
+                .field("exec_time", &seconds(report.get_duration()))
+                .field("stdout", &string(&message));
+        }
+        ExampleResult::Ignored => {
+            object.field("event", &string("ignored"));
+        }
+        ExampleResult::Pending(ref reason) => {
+            object.field("event", &string("ignored"));
+            if let Some(ref reason) = reason {
+                object.field("message", &string(reason));
+            }
+        }
+        ExampleResult::NotRun => {
+            object
+                .field("event", &string("ignored"))
+                .field("message", &string("not run"));
+        }
+        // Examples only get filtered out without being started, unless they return `Filtered`:
+        ExampleResult::Filtered => {
+            object
+                .field("event", &string("ignored"))
+                .field("message", &string("filtered out"));
+        }
+    }
+    object.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use block::suite;
+    use header::*;
+    use runner::{ConfigurationBuilder, Filter};
+    use time::Duration;
+
+    fn lines(logger: &LibtestJsonLogger<Vec<u8>>) -> Vec<String> {
+        let buffer = logger.buffer.lock().unwrap();
+        String::from_utf8_lossy(&buffer)
+            .lines()
+            .map(|line| line.to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn it_writes_test_events_as_they_happen() {
+        // arrange
+        let logger = LibtestJsonLogger::new(vec![]);
+        let runner = Runner::default();
+        let mut header = ExampleHeader::new(ExampleLabel::It, "fails");
+        header.full_name = Some("suite > context > fails".to_owned());
+        let failure = ExampleResult::Failure(Some("reason".to_owned()));
+        let pending = ExampleResult::Pending(Some("later".to_owned()));
+        // act
+        logger.enter_example(&runner, &header);
+        let started = lines(&logger);
+        logger.exit_example(
+            &runner,
+            &header,
+            &ExampleReport::new(failure, Duration::milliseconds(500)),
+        );
+        logger.exit_example(
+            &runner,
+            &header,
+            &ExampleReport::new(pending, Duration::zero()),
+        );
+        // assert
+        let expected = vec![
+            r#"{"type":"test","event":"started","name":"suite > context > fails"}"#,
+            r#"{"type":"test","name":"suite > context > fails","event":"failed","exec_time":0.5,"stdout":"reason"}"#,
+            r#"{"type":"test","name":"suite > context > fails","event":"ignored","message":"later"}"#,
+        ];
+        assert_eq!(started, &expected[..1]);
+        assert_eq!(lines(&logger), expected);
+    }
+
+    #[test]
+    fn it_writes_suite_events() {
+        // arrange
+        let config = ConfigurationBuilder::default()
+            .parallel(false)
+            .filter(Filter::substring("passes"))
+            .build()
+            .unwrap();
+        let logger = Arc::new(LibtestJsonLogger::new(vec![]));
+        let runner = Runner::new(config, vec![logger.clone()]);
+        let suite = suite("suite", (), |ctx| {
+            ctx.it("passes", |_| {});
+            ctx.it("is filtered", |_| {});
+            ctx.scope(|ctx| {
+                ctx.it("passes", |_| {});
+            });
+        });
+        // act
+        runner.run(&suite);
+        // assert
+        let lines = lines(&logger);
+        assert_eq!(lines.len(), 6);
+        assert_eq!(
+            lines[0],
+            r#"{"type":"suite","event":"started","test_count":2}"#
+        );
+        assert_eq!(
+            lines[3],
+            r#"{"type":"test","event":"started","name":"suite > passes (2)"}"#
+        );
+        assert!(lines[5].starts_with(
+            r#"{"type":"suite","event":"ok","passed":2,"failed":0,"ignored":0,"measured":0,"filtered_out":1,"#
+        ));
+    }
+
+    #[test]
+    fn it_ignores_examples_returning_filtered() {
+        // arrange
+        let config = ConfigurationBuilder::default()
+            .parallel(false)
+            .build()
+            .unwrap();
+        let logger = Arc::new(LibtestJsonLogger::new(vec![]));
+        let runner = Runner::new(config, vec![logger.clone()]);
+        let suite = suite("suite", (), |ctx| {
+            ctx.it("is filtered", |_| ExampleResult::Filtered);
+        });
+        // act
+        runner.run(&suite);
+        // assert
+        let lines = lines(&logger);
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[2],
+            r#"{"type":"test","name":"suite > is filtered","event":"ignored","message":"filtered out"}"#
+        );
+        assert!(lines[3].starts_with(r#"{"type":"suite","event":"ok","passed":0,"failed":0,"#));
+    }
+}
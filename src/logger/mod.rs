@@ -15,18 +15,58 @@
 //! # }
 //! ```
 
+mod collected;
+#[cfg(test)]
+mod fixtures;
+mod json;
+mod junit;
+mod libtest;
 mod serial;
+mod tap;
+mod timing;
 
 use std::io;
+use std::ops::DerefMut;
+use std::sync::Mutex;
+
+use colored::*;
+
+pub use logger::json::JsonLogger;
+pub use logger::junit::JUnitLogger;
+pub use logger::libtest::LibtestJsonLogger;
+pub use logger::tap::TapLogger;
 
 use header::{ContextHeader, ExampleHeader, SuiteHeader};
 use logger::serial::SerialLogger;
-use report::{BlockReport, ContextReport, ExampleReport, SuiteReport};
+use report::{BlockReport, ContextReport, ExampleReport, RunReport, SuiteReport};
 use runner::{Runner, RunnerObserver};
 
+/// Gives `accessor` exclusive access to a logger's buffer (or state),
+/// reporting I/O errors on stderr rather than failing the run.
+pub(crate) fn access<S, F>(mutex: &Mutex<S>, accessor: F)
+where
+    F: FnOnce(&mut S) -> io::Result<()>,
+{
+    if let Ok(ref mut mutex_guard) = mutex.lock() {
+        let result = accessor(mutex_guard.deref_mut());
+        if let Err(error) = result {
+            // TODO: better error handling
+            eprintln!("\n{}: {:?}", "error".red().bold(), error);
+        }
+    } else {
+        // TODO: better error handling
+        eprintln!(
+            "\n{}: failed to acquire lock on mutex.",
+            "error".red().bold()
+        );
+    }
+}
+
 /// Preferred logger for test suite execution.
 pub struct Logger<T: io::Write> {
     serial: SerialLogger<T>,
+    /// Keeps replays of suites evaluated in parallel (e.g. by `Runner::run_all`) from interleaving.
+    replaying: Mutex<()>,
 }
 
 impl<T: io::Write> Logger<T>
@@ -36,16 +76,22 @@ where
     pub fn new(buffer: T) -> Logger<T> {
         Logger {
             serial: SerialLogger::new(buffer),
+            replaying: Mutex::new(()),
         }
     }
 
     fn replay_suite(&self, runner: &Runner, suite: &SuiteHeader, report: &SuiteReport) {
+        let _guard = self.replaying.lock();
         self.serial.enter_suite(runner, suite);
         self.replay_context(runner, None, report.get_context());
         self.serial.exit_suite(runner, suite, report);
     }
 
     fn replay_block(&self, runner: &Runner, report: &BlockReport) {
+        if report.is_skipped() {
+            // Skipped blocks never got entered, so there is nothing to replay.
+            return;
+        }
         match report {
             BlockReport::Context(ref header, ref report) => {
                 self.replay_context(runner, header.as_ref(), report);
@@ -83,6 +129,14 @@ impl<T: io::Write> RunnerObserver for Logger<T>
 where
     T: Send + Sync,
 {
+    fn enter_run(&self, runner: &Runner) {
+        self.serial.enter_run(runner);
+    }
+
+    fn exit_run(&self, runner: &Runner, report: &RunReport) {
+        self.serial.exit_run(runner, report);
+    }
+
     fn enter_suite(&self, runner: &Runner, header: &SuiteHeader) {
         if runner.configuration.parallel {
             // If the suite is being evaluated in parallel we basically wait for `exit_suite`.
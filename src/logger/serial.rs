@@ -1,5 +1,4 @@
 use std::io;
-use std::ops::DerefMut;
 use std::sync::Mutex;
 
 use time::Duration;
@@ -7,7 +6,12 @@ use time::Duration;
 use colored::*;
 
 use header::{ContextHeader, ExampleHeader, SuiteHeader};
-use report::{BlockReport, ContextReport, ExampleReport, ExampleResult, Report, SuiteReport};
+use logger;
+use logger::timing::{self, Timing};
+use report::{
+    BlockReport, ContextReport, ExampleReport, ExampleResult, HookFailure, Report, RunReport,
+    SuiteReport,
+};
 use runner::{Runner, RunnerObserver};
 
 #[derive(new)]
@@ -15,6 +19,9 @@ struct SerialLoggerState<T: io::Write = io::Stdout> {
     buffer: T,
     #[new(value = "0")]
     level: usize,
+    /// Whether the suites are part of a run, sharing its prefix and summary.
+    #[new(value = "false")]
+    in_run: bool,
 }
 
 /// Preferred logger for serial test suite execution
@@ -41,23 +48,25 @@ impl<T: io::Write> SerialLogger<T> {
         "  ".repeat(depth)
     }
 
-    fn access_state<F>(&self, mut accessor: F)
+    fn access_state<F>(&self, accessor: F)
     where
-        F: FnMut(&mut SerialLoggerState<T>) -> io::Result<()>,
+        F: FnOnce(&mut SerialLoggerState<T>) -> io::Result<()>,
     {
-        if let Ok(ref mut mutex_guard) = self.state.lock() {
-            let result = accessor(mutex_guard.deref_mut());
-            if let Err(error) = result {
-                // TODO: better error handling
-                eprintln!("\n{}: {:?}", "error".red().bold(), error);
+        logger::access(&self.state, accessor);
+    }
+
+    fn write_failures(&self, buffer: &mut T, reports: &[SuiteReport]) -> io::Result<()> {
+        if reports
+            .iter()
+            .any(|report| num_failures(report.get_context()) > 0)
+        {
+            let _ = writeln!(buffer, "\nfailures:\n");
+            for report in reports {
+                self.write_suite_failures(buffer, 0, report)?;
             }
-        } else {
-            // TODO: better error handling
-            eprintln!(
-                "\n{}: failed to aquire lock on mutex.",
-                "error".red().bold()
-            );
         }
+
+        Ok(())
     }
 
     fn write_suite_failures(
@@ -66,10 +75,11 @@ impl<T: io::Write> SerialLogger<T> {
         indent: usize,
         report: &SuiteReport,
     ) -> io::Result<()> {
-        if report.is_failure() {
-            let _ = writeln!(buffer, "\nfailures:\n");
+        let context_report = report.get_context();
+        if num_failures(context_report) > 0 {
             writeln!(buffer, "{}{}", Self::padding(indent), report.get_header())?;
-            let context_report = report.get_context();
+            let hook_failures = context_report.get_hook_failures();
+            self.write_hook_failures(buffer, indent + 1, hook_failures)?;
             for block_report in context_report.get_blocks() {
                 self.write_block_failures(buffer, indent + 1, block_report)?;
             }
@@ -84,7 +94,11 @@ impl<T: io::Write> SerialLogger<T> {
         indent: usize,
         report: &BlockReport,
     ) -> io::Result<()> {
-        if report.is_failure() {
+        let num_failures = match report {
+            BlockReport::Context(_, ref report) => num_failures(report),
+            BlockReport::Example(_, ref report) => report.get_failed(),
+        };
+        if num_failures > 0 {
             match report {
                 BlockReport::Context(ref header, ref report) => {
                     if let Some(header) = header.as_ref() {
@@ -94,6 +108,9 @@ impl<T: io::Write> SerialLogger<T> {
                 }
                 BlockReport::Example(ref header, ref report) => {
                     writeln!(buffer, "{}{}", Self::padding(indent), header)?;
+                    if let Some(location) = header.location {
+                        writeln!(buffer, "{}at {}", Self::padding(indent + 1), location)?;
+                    }
                     self.write_example_failure(buffer, indent + 1, report)?;
                 }
             }
@@ -107,8 +124,9 @@ impl<T: io::Write> SerialLogger<T> {
         indent: usize,
         report: &ContextReport,
     ) -> io::Result<()> {
-        if report.is_failure() {
+        if num_failures(report) > 0 {
             writeln!(buffer)?;
+            self.write_hook_failures(buffer, indent + 1, report.get_hook_failures())?;
             for block_report in report.get_blocks() {
                 self.write_block_failures(buffer, indent + 1, block_report)?;
             }
@@ -117,15 +135,47 @@ impl<T: io::Write> SerialLogger<T> {
         Ok(())
     }
 
+    fn write_hook_failures(
+        &self,
+        buffer: &mut T,
+        indent: usize,
+        failures: &[HookFailure],
+    ) -> io::Result<()> {
+        for failure in failures {
+            let kind = failure.get_kind();
+            writeln!(buffer, "{}{} hook failed", Self::padding(indent), kind)?;
+            if let Some(message) = failure.get_message() {
+                for line in message.lines() {
+                    writeln!(buffer, "{}{}", Self::padding(indent + 1), line)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn write_example_failure(
         &self,
         buffer: &mut T,
         indent: usize,
         report: &ExampleReport,
     ) -> io::Result<()> {
-        if let ExampleResult::Failure(Some(ref reason)) = report.get_result() {
-            let padding = Self::padding(indent);
-            writeln!(buffer, "{}{}", padding, reason)?;
+        let padding = Self::padding(indent);
+        match report.get_result() {
+            ExampleResult::Failure(Some(ref reason)) => {
+                // Reasons may span several lines (e.g. panics' backtraces):
+                for line in reason.lines() {
+                    writeln!(buffer, "{}{}", padding, line)?;
+                }
+            }
+            ExampleResult::TimedOut(timeout) => {
+                let timeout = Self::format_duration(*timeout);
+                writeln!(buffer, "{}timed out after {}", padding, timeout)?;
+            }
+            _ => {}
+        }
+        let attempts = report.get_attempts().len();
+        if attempts > 1 {
+            writeln!(buffer, "{}(failed all {} attempts)", padding, attempts)?;
         }
         Ok(())
     }
@@ -136,19 +186,50 @@ impl<T: io::Write> SerialLogger<T> {
         Ok(())
     }
 
-    fn write_suite_suffix(&self, buffer: &mut T, report: &SuiteReport) -> io::Result<()> {
+    fn write_seed(&self, buffer: &mut T, runner: &Runner) -> io::Result<()> {
+        if let Some(seed) = runner.seed() {
+            writeln!(buffer, "\nrandomized with seed {}", seed)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_suffix<R>(&self, buffer: &mut T, report: &R, is_focused: bool) -> io::Result<()>
+    where
+        R: Report,
+    {
         self.write_duration(buffer, report.get_duration())?;
 
         write!(buffer, "\ntest result: {}.", self.report_flag(report))?;
 
         writeln!(
             buffer,
-            " {} passed; {} failed; {} ignored",
+            " {} passed; {} failed; {} flaky; {} ignored; {} pending; {} filtered out",
             report.get_passed(),
             report.get_failed(),
-            report.get_ignored()
+            report.get_flaky(),
+            report.get_ignored(),
+            report.get_pending(),
+            report.get_filtered()
         )?;
 
+        if report.get_not_run() > 0 {
+            writeln!(
+                buffer,
+                "\n{}: stopped early, {} examples were not run",
+                "warning".yellow().bold(),
+                report.get_not_run()
+            )?;
+        }
+
+        if is_focused {
+            writeln!(
+                buffer,
+                "\n{}: focus is active, only focused examples and contexts were run",
+                "warning".yellow().bold()
+            )?;
+        }
+
         if report.is_failure() {
             writeln!(buffer, "\n{}: test failed", "error".red().bold())?;
         }
@@ -156,7 +237,48 @@ impl<T: io::Write> SerialLogger<T> {
         Ok(())
     }
 
+    fn write_slowest(
+        &self,
+        buffer: &mut T,
+        runner: &Runner,
+        reports: &[SuiteReport],
+    ) -> io::Result<()> {
+        let count = runner.configuration.slowest;
+        if count == 0 {
+            return Ok(());
+        }
+        let examples = timing::slowest_examples(reports, count);
+        self.write_timings(buffer, "slowest examples", &examples)?;
+        let contexts = timing::slowest_contexts(reports, count);
+        self.write_timings(buffer, "slowest contexts", &contexts)?;
+
+        Ok(())
+    }
+
+    fn write_timings(&self, buffer: &mut T, title: &str, timings: &[Timing]) -> io::Result<()> {
+        if timings.is_empty() {
+            return Ok(());
+        }
+        writeln!(buffer, "\n{}:\n", title)?;
+        for timing in timings {
+            let duration = Self::format_duration(timing.duration);
+            writeln!(
+                buffer,
+                "{}{:>10}  {}",
+                Self::padding(1),
+                duration,
+                timing.name
+            )?;
+        }
+
+        Ok(())
+    }
+
     fn write_duration(&self, buffer: &mut T, duration: Duration) -> io::Result<()> {
+        writeln!(buffer, "\nduration: {}.", Self::format_duration(duration))
+    }
+
+    fn format_duration(duration: Duration) -> String {
         let millisecond = 1;
         let second = 1000 * millisecond;
         let minute = 60 * second;
@@ -175,9 +297,21 @@ impl<T: io::Write> SerialLogger<T> {
 
         let milliseconds = remainder / millisecond;
         match (hours, minutes, seconds, milliseconds) {
-            (0, 0, s, ms) => writeln!(buffer, "\nduration: {}.{:03}s.", s, ms),
-            (0, m, s, ms) => writeln!(buffer, "\nduration: {}m {}.{:03}s.", m, s, ms),
-            (h, m, s, ms) => writeln!(buffer, "\nduration: {}h {}m {}.{:03}s.", h, m, s, ms),
+            (0, 0, s, ms) => format!("{}.{:03}s", s, ms),
+            (0, m, s, ms) => format!("{}m {}.{:03}s", m, s, ms),
+            (h, m, s, ms) => format!("{}h {}m {}.{:03}s", h, m, s, ms),
+        }
+    }
+
+    fn example_flag(&self, report: &ExampleReport) -> ColoredString {
+        match report.get_result() {
+            ExampleResult::Pending(None) => "pending".yellow(),
+            ExampleResult::Pending(Some(ref reason)) => format!("pending ({})", reason).yellow(),
+            ExampleResult::TimedOut(_) => "TIMED OUT".red(),
+            _ if report.is_flaky() => {
+                format!("ok (flaky, {} attempts)", report.get_attempts().len()).yellow()
+            }
+            _ => self.report_flag(report),
         }
     }
 
@@ -197,20 +331,52 @@ impl<T: io::Write> RunnerObserver for SerialLogger<T>
 where
     T: Send + Sync,
 {
-    fn enter_suite(&self, _runner: &Runner, header: &SuiteHeader) {
+    fn enter_run(&self, runner: &Runner) {
         self.access_state(|state| {
-            state.level += 1;
+            state.in_run = true;
+            self.write_seed(&mut state.buffer, runner)?;
             self.write_suite_prefix(&mut state.buffer)?;
+
+            Ok(())
+        });
+    }
+
+    fn exit_run(&self, runner: &Runner, report: &RunReport) {
+        self.access_state(|state| {
+            let suites = report.get_suites();
+            self.write_failures(&mut state.buffer, suites)?;
+            self.write_suffix(&mut state.buffer, report, report.is_focused())?;
+            self.write_slowest(&mut state.buffer, runner, suites)?;
+            self.write_seed(&mut state.buffer, runner)?;
+
+            state.in_run = false;
+
+            Ok(())
+        });
+    }
+
+    fn enter_suite(&self, runner: &Runner, header: &SuiteHeader) {
+        self.access_state(|state| {
+            state.level += 1;
+            if !state.in_run {
+                self.write_seed(&mut state.buffer, runner)?;
+                self.write_suite_prefix(&mut state.buffer)?;
+            }
             writeln!(state.buffer, "{}{}", Self::padding(state.level - 1), header)?;
 
             Ok(())
         });
     }
 
-    fn exit_suite(&self, _runner: &Runner, _header: &SuiteHeader, report: &SuiteReport) {
+    fn exit_suite(&self, runner: &Runner, _header: &SuiteHeader, report: &SuiteReport) {
         self.access_state(|state| {
-            self.write_suite_failures(&mut state.buffer, 0, report)?;
-            self.write_suite_suffix(&mut state.buffer, report)?;
+            if !state.in_run {
+                let suites = ::std::slice::from_ref(report);
+                self.write_failures(&mut state.buffer, suites)?;
+                self.write_suffix(&mut state.buffer, report, report.is_focused())?;
+                self.write_slowest(&mut state.buffer, runner, suites)?;
+                self.write_seed(&mut state.buffer, runner)?;
+            }
 
             state.level -= 1;
 
@@ -249,9 +415,20 @@ where
         });
     }
 
-    fn exit_example(&self, _runner: &Runner, _header: &ExampleHeader, report: &ExampleReport) {
+    fn exit_example(&self, runner: &Runner, _header: &ExampleHeader, report: &ExampleReport) {
         self.access_state(|state| {
-            writeln!(state.buffer, "{}", self.report_flag(report))?;
+            write!(state.buffer, "{}", self.example_flag(report))?;
+            if let Some(threshold) = runner.configuration.slow_threshold {
+                if report.get_duration() > threshold {
+                    let duration = Self::format_duration(report.get_duration());
+                    write!(
+                        state.buffer,
+                        " {}",
+                        format!("(slow: {})", duration).yellow()
+                    )?;
+                }
+            }
+            writeln!(state.buffer)?;
             state.level -= 1;
 
             Ok(())
@@ -259,6 +436,11 @@ where
     }
 }
 
+/// The number of failed examples and hooks within a context.
+fn num_failures(report: &ContextReport) -> u32 {
+    report.get_failed() + report.num_hook_failures()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -278,4 +460,178 @@ mod tests {
             }
         }
     }
+
+    mod format_duration {
+        use super::*;
+
+        #[test]
+        fn it_formats() {
+            // arrange
+            let expected = vec![
+                ("0.250s", Duration::milliseconds(250)),
+                ("2m 3.000s", Duration::seconds(123)),
+                ("1h 0m 1.500s", Duration::milliseconds(3_601_500)),
+            ];
+            for (expected_res, given_duration) in expected {
+                // act
+                let res = SerialLogger::<Vec<u8>>::format_duration(given_duration);
+                // assert
+                assert_eq!(String::from(expected_res), res)
+            }
+        }
+    }
+
+    mod run {
+        use super::*;
+
+        use std::sync::Arc;
+
+        use block::suite;
+        use runner::ConfigurationBuilder;
+
+        #[test]
+        fn it_writes_a_single_summary() {
+            // arrange
+            let logger = Arc::new(SerialLogger::new(vec![]));
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![logger.clone()]);
+            let numbers = suite("numbers", 42, |ctx| {
+                ctx.it("is even", |number| number % 2 == 0);
+            });
+            let strings = suite("strings", "hello".to_owned(), |ctx| {
+                ctx.it("is empty", |string| string.is_empty());
+            });
+            // act
+            runner.run_all(&[&numbers, &strings]);
+            // assert
+            let state = logger.state.lock().unwrap();
+            let output = String::from_utf8(state.buffer.clone()).unwrap();
+            assert_eq!(output.matches("tests:").count(), 1);
+            assert_eq!(output.matches("failures:").count(), 1);
+            assert_eq!(output.matches("test result:").count(), 1);
+            assert!(output.contains(" 1 passed; 1 failed;"));
+            assert!(output.contains(&format!("at {}:", file!())));
+        }
+
+        #[test]
+        fn it_writes_hook_failures() {
+            // arrange
+            let logger = Arc::new(SerialLogger::new(vec![]));
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![logger.clone()]);
+            let suite = suite("suite", (), |ctx| {
+                ctx.context("context", |ctx| {
+                    ctx.after_all(|_| panic!("boom"));
+                    ctx.it("passes", |_| {});
+                });
+            });
+            // act
+            runner.run(&suite);
+            // assert
+            let state = logger.state.lock().unwrap();
+            let output = String::from_utf8(state.buffer.clone()).unwrap();
+            let failures = &output[output.find("failures:").unwrap()..];
+            assert!(failures.contains("Context \"context\"\n      after_all hook failed\n"));
+            assert!(failures.contains("        thread panicked at 'boom'"));
+            assert!(output.contains("test result: FAILED."));
+        }
+    }
+
+    mod write_timings {
+        use super::*;
+
+        #[test]
+        fn it_lists_names_and_durations() {
+            // arrange
+            let logger = SerialLogger::new(vec![]);
+            let timings = vec![Timing {
+                name: "suite > example".to_owned(),
+                duration: Duration::milliseconds(1500),
+            }];
+            let mut buffer = vec![];
+            // act
+            logger
+                .write_timings(&mut buffer, "slowest examples", &timings)
+                .unwrap();
+            // assert
+            assert_eq!(
+                "\nslowest examples:\n\n      1.500s  suite > example\n",
+                String::from_utf8(buffer).unwrap()
+            );
+        }
+    }
+
+    mod write_example_failure {
+        use super::*;
+
+        use report::ExampleAttempt;
+
+        #[test]
+        fn it_shows_the_timeout() {
+            // arrange
+            let logger = SerialLogger::new(vec![]);
+            let report = ExampleReport::new(
+                ExampleResult::TimedOut(Duration::milliseconds(1500)),
+                Duration::milliseconds(1500),
+            );
+            let mut buffer = vec![];
+            // act
+            logger
+                .write_example_failure(&mut buffer, 1, &report)
+                .unwrap();
+            // assert
+            assert_eq!(
+                "  timed out after 1.500s\n",
+                String::from_utf8(buffer).unwrap()
+            );
+        }
+
+        #[test]
+        fn it_indents_every_line_of_the_reason() {
+            // arrange
+            let logger = SerialLogger::new(vec![]);
+            let reason = "thread panicked at 'boom', src/lib.rs:1:2\nstack backtrace:";
+            let report = ExampleReport::new(
+                ExampleResult::Failure(Some(reason.to_owned())),
+                Duration::zero(),
+            );
+            let mut buffer = vec![];
+            // act
+            logger
+                .write_example_failure(&mut buffer, 1, &report)
+                .unwrap();
+            // assert
+            assert_eq!(
+                "  thread panicked at 'boom', src/lib.rs:1:2\n  stack backtrace:\n",
+                String::from_utf8(buffer).unwrap()
+            );
+        }
+
+        #[test]
+        fn it_shows_the_number_of_attempts() {
+            // arrange
+            let logger = SerialLogger::new(vec![]);
+            let attempt = ExampleAttempt::new(
+                ExampleResult::Failure(Some("reason".to_owned())),
+                Duration::zero(),
+            );
+            let report = ExampleReport::from_attempts(vec![attempt.clone(), attempt]);
+            let mut buffer = vec![];
+            // act
+            logger
+                .write_example_failure(&mut buffer, 1, &report)
+                .unwrap();
+            // assert
+            assert_eq!(
+                "  reason\n  (failed all 2 attempts)\n",
+                String::from_utf8(buffer).unwrap()
+            );
+        }
+    }
 }
@@ -0,0 +1,490 @@
+use std::io;
+use std::sync::Mutex;
+
+use header::{ExampleHeader, SuiteHeader};
+use logger;
+use logger::collected::Collected;
+use logger::json;
+use report::{
+    BlockReport, ContextReport, ExampleReport, ExampleResult, HookFailure, Report, RunReport,
+    SuiteReport,
+};
+use runner::{Runner, RunnerObserver};
+
+/// Logger writing a test suite's results in the [TAP version 14](https://testanything.org/tap-version-14-specification.html) format.
+///
+/// Each suite gets written as a TAP document whose nesting mirrors the suite's:
+/// contexts become (indented) subtests and examples become test points.
+/// Ignored and not run examples get marked as `# SKIP`, pending ones as `# TODO`
+/// and failure messages go into YAML diagnostic blocks.
+/// Failed hooks become failed test points of their own (e.g. `not ok 1 - after_all hook`).
+/// Filtered out examples are omitted, as they are not part of the run.
+/// The document gets written once the logger gets dropped, usually along with the runner.
+/// If the runner ran several suites (be they run one by one or together, see [`Runner::run_all`](../struct.Runner.html#method.run_all)),
+/// they get written as a single document, with a subtest per suite.
+///
+/// ```text
+/// TAP version 14
+/// # Suite "a suite"
+///     # Subtest: a context
+///     ok 1 - an example
+///     not ok 2 - a failing example
+///       ---
+///       message: "assertion failed: `expected condition to be true`"
+///       severity: fail
+///       at: {"file":"tests/specs.rs","line":12,"column":13}
+///       duration_ms: 0
+///       ...
+///     1..2
+/// not ok 1 - a context
+/// 1..1
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// # extern crate rspec;
+/// #
+/// # use std::io;
+/// # use std::sync::Arc;
+/// #
+/// # pub fn main() {
+/// let logger = Arc::new(rspec::logger::TapLogger::new(io::stdout()));
+/// let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+/// let runner = rspec::Runner::new(configuration, vec![logger]);
+/// # }
+/// ```
+pub struct TapLogger<T: io::Write = io::Stdout> {
+    buffer: Mutex<T>,
+    collected: Collected,
+}
+
+impl<T: io::Write> TapLogger<T> {
+    pub fn new(buffer: T) -> Self {
+        TapLogger {
+            buffer: Mutex::new(buffer),
+            collected: Collected::new(),
+        }
+    }
+
+    fn write(&self, tap: &str) {
+        logger::access(&self.buffer, |buffer| {
+            buffer
+                .write_all(tap.as_bytes())
+                .and_then(|_| buffer.flush())
+        });
+    }
+}
+
+impl<T: io::Write> RunnerObserver for TapLogger<T>
+where
+    T: Send + Sync,
+{
+    fn enter_run(&self, _runner: &Runner) {
+        self.collected.enter_run();
+    }
+
+    fn exit_run(&self, _runner: &Runner, report: &RunReport) {
+        self.collected.exit_run(report);
+    }
+
+    fn exit_suite(&self, _runner: &Runner, _header: &SuiteHeader, report: &SuiteReport) {
+        self.collected.exit_suite(report);
+    }
+}
+
+impl<T: io::Write> Drop for TapLogger<T> {
+    fn drop(&mut self) {
+        // A document has a single version line and plan, so all suites get written at once:
+        if let Some(report) = self.collected.take() {
+            match report.get_suites() {
+                [suite] => self.write(&document(suite)),
+                _ => self.write(&run_document(&report)),
+            }
+        }
+    }
+}
+
+fn padding(depth: usize) -> String {
+    "    ".repeat(depth)
+}
+
+/// Returns `description` escaped for use in a test point
+/// (where `#` would otherwise start a directive).
+fn escape(description: &str) -> String {
+    description
+        .replace('\\', "\\\\")
+        .replace('#', "\\#")
+        .replace('\n', " ")
+}
+
+/// Whether a block ends up as (or containing) test points.
+fn has_test_points<R: Report>(report: &R) -> bool {
+    report.get_passed()
+        + report.get_failed()
+        + report.get_ignored()
+        + report.get_pending()
+        + report.get_not_run()
+        > 0
+}
+
+fn document(report: &SuiteReport) -> String {
+    let mut tap = String::new();
+    tap.push_str("TAP version 14\n");
+    tap.push_str(&format!("# {}\n", report.get_header()));
+    let mut count = 0;
+    write_blocks(&mut tap, 0, &mut count, report.get_context());
+    tap.push_str(&format!("1..{}\n", count));
+    tap
+}
+
+fn run_document(report: &RunReport) -> String {
+    let mut tap = String::new();
+    tap.push_str("TAP version 14\n");
+    let mut count = 0;
+    for suite in report.get_suites() {
+        if !has_test_points(suite) {
+            continue;
+        }
+        write_subtest(
+            &mut tap,
+            0,
+            &mut count,
+            suite.get_header().name,
+            suite.get_context(),
+        );
+    }
+    tap.push_str(&format!("1..{}\n", count));
+    tap
+}
+
+fn write_subtest(
+    tap: &mut String,
+    depth: usize,
+    count: &mut u32,
+    name: &str,
+    report: &ContextReport,
+) {
+    let name = escape(name);
+    tap.push_str(&format!("{}# Subtest: {}\n", padding(depth + 1), name));
+    let mut subtest_count = 0;
+    write_blocks(tap, depth + 1, &mut subtest_count, report);
+    tap.push_str(&format!("{}1..{}\n", padding(depth + 1), subtest_count));
+    *count += 1;
+    let status = if report.get_failed() == 0 && report.num_hook_failures() == 0 {
+        "ok"
+    } else {
+        "not ok"
+    };
+    tap.push_str(&format!(
+        "{}{} {} - {}\n",
+        padding(depth),
+        status,
+        count,
+        name
+    ));
+}
+
+fn write_blocks(tap: &mut String, depth: usize, count: &mut u32, report: &ContextReport) {
+    for failure in report.get_hook_failures() {
+        *count += 1;
+        write_hook_failure(tap, depth, *count, failure);
+    }
+    for block in report.get_blocks() {
+        if !has_test_points(block) {
+            continue;
+        }
+        match block {
+            BlockReport::Context(Some(ref header), ref report) => {
+                write_subtest(tap, depth, count, header.name, report);
+            }
+            BlockReport::Context(None, ref report) => {
+                // Name-less contexts don't show up in the logs, so their blocks get flattened:
+                write_blocks(tap, depth, count, report);
+            }
+            BlockReport::Example(ref header, ref report) => {
+                *count += 1;
+                write_example(tap, depth, *count, header, report);
+            }
+        }
+    }
+}
+
+fn write_hook_failure(tap: &mut String, depth: usize, number: u32, failure: &HookFailure) {
+    let padding = padding(depth);
+    tap.push_str(&format!(
+        "{}not ok {} - {} hook\n",
+        padding,
+        number,
+        failure.get_kind()
+    ));
+    tap.push_str(&format!("{}  ---\n", padding));
+    if let Some(message) = failure.get_message() {
+        tap.push_str(&format!(
+            "{}  message: {}\n",
+            padding,
+            json::string(message)
+        ));
+    }
+    tap.push_str(&format!("{}  severity: fail\n", padding));
+    tap.push_str(&format!("{}  ...\n", padding));
+}
+
+fn write_example(
+    tap: &mut String,
+    depth: usize,
+    number: u32,
+    header: &ExampleHeader,
+    report: &ExampleReport,
+) {
+    let padding = padding(depth);
+    let name = escape(header.name);
+    let (status, directive) = match report.get_result() {
+        ExampleResult::Success => ("ok", String::new()),
+        ExampleResult::Failure(_) | ExampleResult::TimedOut(_) => ("not ok", String::new()),
+        ExampleResult::Ignored => ("ok", " # SKIP".to_owned()),
+        ExampleResult::NotRun => ("ok", " # SKIP not run".to_owned()),
+        ExampleResult::Pending(None) => ("not ok", " # TODO".to_owned()),
+        ExampleResult::Pending(Some(ref reason)) => {
+            ("not ok", format!(" # TODO {}", escape(reason)))
+        }
+        ExampleResult::Filtered => return,
+    };
+    tap.push_str(&format!(
+        "{}{} {} - {}{}\n",
+        padding, status, number, name, directive
+    ));
+    let message = match report.get_result() {
+        ExampleResult::Failure(ref message) => message.clone(),
+        ExampleResult::TimedOut(timeout) => {
+            Some(format!("timed out after {}s", timeout.as_seconds_f64()))
+        }
+        _ => return,
+    };
+    // YAML diagnostic blocks are indented by two spaces relative to their test point:
+    tap.push_str(&format!("{}  ---\n", padding));
+    if let Some(message) = message {
+        // JSON strings are valid YAML (flow scalars):
+        tap.push_str(&format!(
+            "{}  message: {}\n",
+            padding,
+            json::string(&message)
+        ));
+    }
+    tap.push_str(&format!("{}  severity: fail\n", padding));
+    if header.location.is_some() {
+        // So are JSON objects (as flow mappings):
+        tap.push_str(&format!(
+            "{}  at: {}\n",
+            padding,
+            json::location(header.location)
+        ));
+    }
+    tap.push_str(&format!(
+        "{}  duration_ms: {}\n",
+        padding,
+        report.get_duration().whole_milliseconds()
+    ));
+    if report.get_attempts().len() > 1 {
+        tap.push_str(&format!(
+            "{}  attempts: {}\n",
+            padding,
+            report.get_attempts().len()
+        ));
+    }
+    tap.push_str(&format!("{}  ...\n", padding));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use block::suite;
+    use header::*;
+    use logger::fixtures::{example, SharedBuffer};
+    use report::*;
+    use runner::ConfigurationBuilder;
+    use time::Duration;
+
+    #[test]
+    fn it_escapes_descriptions() {
+        assert_eq!(escape("a #1 \\o/"), "a \\#1 \\\\o/");
+    }
+
+    #[test]
+    fn it_writes_nested_subtests() {
+        // arrange
+        let inner = ContextReport::new(
+            vec![example("is pending", ExampleResult::Pending(None), 5)],
+            Duration::zero(),
+        );
+        let context = ContextReport::new(
+            vec![
+                example("passes", ExampleResult::Success, 5),
+                example(
+                    "fails",
+                    ExampleResult::Failure(Some("reason".to_owned())),
+                    5,
+                ),
+                BlockReport::Context(Some(ContextHeader::new(ContextLabel::When, "inner")), inner),
+            ],
+            Duration::zero(),
+        );
+        let scope = ContextReport::new(
+            vec![
+                example("is ignored", ExampleResult::Ignored, 5),
+                example("is filtered", ExampleResult::Filtered, 5),
+            ],
+            Duration::zero(),
+        );
+        let root = ContextReport::new(
+            vec![
+                BlockReport::Context(
+                    Some(ContextHeader::new(ContextLabel::Context, "context")),
+                    context,
+                ),
+                BlockReport::Context(None, scope),
+            ],
+            Duration::zero(),
+        );
+        let report = SuiteReport::new(SuiteHeader::new(SuiteLabel::Suite, "suite"), root, false);
+        // act
+        let tap = document(&report);
+        // assert
+        let expected = "TAP version 14
+# Suite \"suite\"
+    # Subtest: context
+    ok 1 - passes
+    not ok 2 - fails
+      ---
+      message: \"reason\"
+      severity: fail
+      duration_ms: 5
+      ...
+        # Subtest: inner
+        not ok 1 - is pending # TODO
+        1..1
+    ok 3 - inner
+    1..3
+not ok 1 - context
+ok 2 - is ignored # SKIP
+1..2
+";
+        assert_eq!(tap, expected);
+    }
+
+    #[test]
+    fn it_writes_runs_as_subtests() {
+        // arrange
+        let suite = |name, result| {
+            let root = ContextReport::new(vec![example("example", result, 5)], Duration::zero());
+            SuiteReport::new(SuiteHeader::new(SuiteLabel::Suite, name), root, false)
+        };
+        let report = RunReport::new(
+            vec![
+                suite("a", ExampleResult::Success),
+                suite("b", ExampleResult::Filtered),
+                suite("c", ExampleResult::Failure(None)),
+            ],
+            Duration::zero(),
+        );
+        // act
+        let tap = run_document(&report);
+        // assert
+        let expected = "TAP version 14
+    # Subtest: a
+    ok 1 - example
+    1..1
+ok 1 - a
+    # Subtest: c
+    not ok 1 - example
+      ---
+      severity: fail
+      duration_ms: 5
+      ...
+    1..1
+not ok 2 - c
+1..2
+";
+        assert_eq!(tap, expected);
+    }
+
+    #[test]
+    fn it_writes_a_single_document_across_runs() {
+        // arrange
+        let buffer = SharedBuffer::default();
+        let config = ConfigurationBuilder::default().build().unwrap();
+        let logger = Arc::new(TapLogger::new(buffer.clone()));
+        let runner = Runner::new(config, vec![logger]);
+        // act
+        runner.run(&suite("first", (), |ctx| ctx.it("passes", |_| {})));
+        runner.run(&suite("second", (), |ctx| ctx.it("passes", |_| {})));
+        drop(runner);
+        // assert
+        let expected = "TAP version 14
+    # Subtest: first
+    ok 1 - passes
+    1..1
+ok 1 - first
+    # Subtest: second
+    ok 1 - passes
+    1..1
+ok 2 - second
+1..2
+";
+        assert_eq!(buffer.contents(), expected);
+    }
+
+    #[test]
+    fn it_writes_hook_failures_as_test_points() {
+        // arrange
+        let failure = HookFailure::new(HookKind::AfterAll, Some("boom".to_owned()));
+        let root = ContextReport::new(
+            vec![example("passes", ExampleResult::Success, 5)],
+            Duration::zero(),
+        )
+        .with_hook_failures(vec![failure]);
+        let report = SuiteReport::new(SuiteHeader::new(SuiteLabel::Suite, "suite"), root, false);
+        // act
+        let tap = document(&report);
+        // assert
+        let expected = "TAP version 14
+# Suite \"suite\"
+not ok 1 - after_all hook
+  ---
+  message: \"boom\"
+  severity: fail
+  ...
+ok 2 - passes
+1..2
+";
+        assert_eq!(tap, expected);
+    }
+
+    #[test]
+    fn it_writes_the_location_of_failures() {
+        // arrange
+        let report = ExampleReport::new(ExampleResult::Failure(None), Duration::zero());
+        let mut header = ExampleHeader::new(ExampleLabel::It, "fails");
+        let location = ::std::panic::Location::caller();
+        header.location = Some(location);
+        let mut tap = String::new();
+        // act
+        write_example(&mut tap, 0, 1, &header, &report);
+        // assert
+        let expected = format!(
+            "not ok 1 - fails
+  ---
+  severity: fail
+  at: {{\"file\":\"src/logger/tap.rs\",\"line\":{},\"column\":{}}}
+  duration_ms: 0
+  ...
+",
+            location.line(),
+            location.column()
+        );
+        assert_eq!(tap, expected);
+    }
+}
@@ -0,0 +1,136 @@
+//! Timing statistics gathered from a suite's report, for spotting slow examples and contexts.
+
+use time::Duration;
+
+use report::{BlockReport, ContextReport, Report, SuiteReport};
+
+/// The full name and duration of an executed example or context.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) struct Timing {
+    pub name: String,
+    pub duration: Duration,
+}
+
+/// Returns up to `count` of the suites' slowest examples, slowest first.
+pub(crate) fn slowest_examples(reports: &[SuiteReport], count: usize) -> Vec<Timing> {
+    let mut timings = vec![];
+    for report in reports {
+        let name = report.get_header().name;
+        collect_examples(report.get_context(), name.to_owned(), &mut timings);
+    }
+    slowest(timings, count)
+}
+
+/// Returns up to `count` of the suites' slowest (named) contexts, slowest first.
+pub(crate) fn slowest_contexts(reports: &[SuiteReport], count: usize) -> Vec<Timing> {
+    let mut timings = vec![];
+    for report in reports {
+        let name = report.get_header().name;
+        collect_contexts(report.get_context(), name.to_owned(), &mut timings);
+    }
+    slowest(timings, count)
+}
+
+fn slowest(mut timings: Vec<Timing>, count: usize) -> Vec<Timing> {
+    // Sorting is stable, so equally slow blocks stay in the order they were declared in:
+    timings.sort_by_key(|timing| ::std::cmp::Reverse(timing.duration));
+    timings.truncate(count);
+    timings
+}
+
+fn is_executed<R: Report>(report: &R) -> bool {
+    report.get_passed() + report.get_failed() + report.get_ignored() > 0
+}
+
+fn child_name(parent: &str, name: &str) -> String {
+    format!("{} > {}", parent, name)
+}
+
+fn collect_examples(report: &ContextReport, name: String, timings: &mut Vec<Timing>) {
+    for block in report.get_blocks() {
+        match block {
+            BlockReport::Context(ref header, ref report) => {
+                let name = match header {
+                    Some(ref header) => child_name(&name, header.name),
+                    None => name.clone(),
+                };
+                collect_examples(report, name, timings);
+            }
+            BlockReport::Example(ref header, ref report) => {
+                if is_executed(report) {
+                    timings.push(Timing {
+                        name: child_name(&name, header.name),
+                        duration: report.get_duration(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn collect_contexts(report: &ContextReport, name: String, timings: &mut Vec<Timing>) {
+    for block in report.get_blocks() {
+        if let BlockReport::Context(ref header, ref report) = block {
+            let name = match header {
+                Some(ref header) => {
+                    let name = child_name(&name, header.name);
+                    if is_executed(report) {
+                        timings.push(Timing {
+                            name: name.clone(),
+                            duration: report.get_duration(),
+                        });
+                    }
+                    name
+                }
+                None => name.clone(),
+            };
+            collect_contexts(report, name, timings);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use header::*;
+    use logger::fixtures::example;
+    use report::*;
+
+    fn fixture() -> SuiteReport {
+        let context = ContextReport::new(
+            vec![
+                example("b", ExampleResult::Success, 30),
+                example("c", ExampleResult::Success, 20),
+            ],
+            Duration::milliseconds(50),
+        );
+        let root = ContextReport::new(
+            vec![
+                example("a", ExampleResult::Success, 10),
+                BlockReport::Context(
+                    Some(ContextHeader::new(ContextLabel::Context, "context")),
+                    context,
+                ),
+                example("d", ExampleResult::Filtered, 0),
+            ],
+            Duration::milliseconds(60),
+        );
+        SuiteReport::new(SuiteHeader::new(SuiteLabel::Suite, "suite"), root, false)
+    }
+
+    #[test]
+    fn it_lists_the_slowest_examples() {
+        let timings = slowest_examples(&[fixture()], 2);
+        let names: Vec<_> = timings.iter().map(|timing| timing.name.as_str()).collect();
+        assert_eq!(names, vec!["suite > context > b", "suite > context > c"]);
+        assert_eq!(timings[0].duration, Duration::milliseconds(30));
+    }
+
+    #[test]
+    fn it_lists_the_slowest_contexts() {
+        let timings = slowest_contexts(&[fixture()], 10);
+        let names: Vec<_> = timings.iter().map(|timing| timing.name.as_str()).collect();
+        assert_eq!(names, vec!["suite > context"]);
+    }
+}
@@ -0,0 +1,179 @@
+/// Declares a test suite with a nested syntax, sparing the closures and `ctx` threading
+/// of calling [`suite`](fn.suite.html)/[`describe`](fn.describe.html)/[`given`](fn.given.html)
+/// and [`Context`](block/struct.Context.html)'s methods directly.
+///
+/// A suite is declared by its label (`suite`, `describe` or `given`), its name,
+/// its (optional) environment in parentheses and its blocks in braces.
+/// Each block is any of `Context`'s methods, followed by
+///
+/// - a name and blocks in braces, for contexts (e.g. `context`, `specify`, `when`),
+/// - a name and a closure, for examples (e.g. `it`, `example`, `then`),
+/// - a closure, for hooks (e.g. `before_each`, `after_all`, `around_each`),
+/// - blocks in braces, for scopes (i.e. `scope`).
+///
+/// Closures of examples and hooks are `move` closures, whose bodies are either blocks
+/// or expressions terminated by `;`. They get expanded as they are,
+/// so failures point at the lines they are written on.
+/// The suite and each of its blocks get the location of their own label or method
+/// (see [`ExampleHeader::location`](header/struct.ExampleHeader.html#structfield.location)),
+/// so `--at file:line` selects them just like blocks declared by calling the methods.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate rspec;
+///
+/// pub fn main() -> rspec::RunSummary {
+///     let suite = rspec! {
+///         describe "a vector" (vec![1, 2, 3]) {
+///             it "is not empty" |env| !env.is_empty();
+///
+///             context "when cleared" {
+///                 before_each |env| {
+///                     env.clear();
+///                 }
+///
+///                 it "is empty" |env| {
+///                     assert!(env.is_empty());
+///                 }
+///             }
+///         }
+///     };
+///
+///     rspec::run(&suite)
+/// }
+/// ```
+#[macro_export]
+macro_rules! rspec {
+    ($($tokens:tt)*) => {
+        $crate::__rspec!($crate; $($tokens)*)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::Location;
+    use std::sync::{Arc, Mutex};
+
+    use block::Block;
+    use report::Report;
+    use runner::{ConfigurationBuilder, Runner};
+
+    fn runner() -> Runner {
+        let config = ConfigurationBuilder::default()
+            .parallel(false)
+            .build()
+            .unwrap();
+        Runner::new(config, vec![])
+    }
+
+    #[test]
+    fn it_expands_to_builder_calls() {
+        // arrange
+        let events = Arc::new(Mutex::new(vec![]));
+        let (e1, e2, e3) = (events.clone(), events.clone(), events.clone());
+        let suite = rspec! {
+            given "a number" (2) {
+                before_all |_| e1.lock().unwrap().push("before_all");
+                then "is even" |number| number % 2 == 0;
+                when "doubled" {
+                    before_each |number| {
+                        *number *= 2;
+                    }
+                    then "is four" |number| {
+                        assert_eq!(*number, 4);
+                    }
+                    then "is five" |number| *number == 5;
+                }
+                scope {
+                    after_each |_| {
+                        e2.lock().unwrap().push("after_each");
+                    }
+                    it "is not doubled" |number| {
+                        e3.lock().unwrap().push("example");
+                        assert_eq!(*number, 2);
+                    }
+                }
+            }
+        };
+        // act
+        let report = runner().run(&suite);
+        // assert
+        assert_eq!(report.get_header().name, "a number");
+        assert_eq!(report.get_passed(), 3);
+        assert_eq!(report.get_failed(), 1);
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec!["before_all", "example", "after_each"]
+        );
+    }
+
+    #[test]
+    fn it_expands_around_hooks() {
+        // arrange
+        let events = Arc::new(Mutex::new(vec![]));
+        let (e1, e2) = (events.clone(), events.clone());
+        let suite = rspec! {
+            describe "a suite" {
+                around_each |env, block| {
+                    e1.lock().unwrap().push("enter");
+                    block(env);
+                    e1.lock().unwrap().push("exit");
+                }
+                it "passes" |_| {
+                    e2.lock().unwrap().push("example");
+                }
+            }
+        };
+        // act
+        let report = runner().run(&suite);
+        // assert
+        assert_eq!(report.get_passed(), 1);
+        assert_eq!(*events.lock().unwrap(), vec!["enter", "example", "exit"]);
+    }
+
+    #[test]
+    fn it_defaults_to_the_unit_environment() {
+        let suite = rspec! {
+            describe "a suite" {
+                it "passes" |_| {}
+            }
+        };
+        assert_eq!(runner().run(&suite).get_passed(), 1);
+    }
+
+    #[test]
+    fn it_locates_blocks_at_their_declarations() {
+        // arrange
+        let line = line!();
+        let suite = rspec! {
+            describe "a suite" {
+                context "a context" {
+                    it "passes" |_| {}
+                }
+            }
+        };
+        // act
+        let context = match suite.context.blocks[0] {
+            Block::Context(ref context) => context,
+            Block::Example(_) => unreachable!(),
+        };
+        let example = match context.blocks[0] {
+            Block::Example(ref example) => example,
+            Block::Context(_) => unreachable!(),
+        };
+        // assert
+        let locations = vec![
+            suite.header.location,
+            context.header.as_ref().unwrap().location,
+            example.header.location,
+        ];
+        let lines: Vec<_> = locations
+            .into_iter()
+            .map(|l| l.map(Location::line))
+            .collect();
+        assert_eq!(lines, vec![Some(line + 2), Some(line + 3), Some(line + 4)]);
+        assert_eq!(example.header.location.unwrap().file(), file!());
+    }
+}
@@ -0,0 +1,167 @@
+//! Registration of test suites, for running the suites defined across a crate's modules
+//! without having to call each of them from `main`.
+//!
+//! Suites get registered with [`register!`](../macro.register.html) and collected at link time,
+//! so that a harness generated with [`main!`](../macro.main.html) can discover and run them.
+//!
+//! # Examples
+//!
+//! ```toml
+//! # Cargo.toml
+//! [[test]]
+//! name = "specs"
+//! harness = false
+//! ```
+//!
+//! ```no_run
+//! // tests/specs.rs
+//! #[macro_use]
+//! extern crate rspec;
+//!
+//! mod numbers {
+//!     pub fn suite() -> rspec::block::Suite<i32> {
+//!         rspec::describe("a number", 42, |ctx| {
+//!             ctx.it("is even", |number| number % 2 == 0);
+//!         })
+//!     }
+//!
+//!     register!(suite);
+//! }
+//!
+//! mod strings {
+//!     pub fn suite() -> rspec::block::Suite<String> {
+//!         rspec::describe("a string", "hello".to_owned(), |ctx| {
+//!             ctx.it("is lowercase", |string| string.to_lowercase() == *string);
+//!         })
+//!     }
+//!
+//!     register!(suite);
+//! }
+//!
+//! // Runs all registered suites, according to the command-line arguments:
+//! main!();
+//! ```
+
+use runner::RunnableSuite;
+
+/// A registered test suite (see [`register!`](../macro.register.html)).
+pub struct Registration {
+    build: fn() -> Box<dyn RunnableSuite>,
+    module_path: &'static str,
+}
+
+impl Registration {
+    #[doc(hidden)]
+    pub const fn new(build: fn() -> Box<dyn RunnableSuite>, module_path: &'static str) -> Self {
+        Registration { build, module_path }
+    }
+
+    /// Builds the registered suite.
+    pub fn build(&self) -> Box<dyn RunnableSuite> {
+        (self.build)()
+    }
+
+    /// The path of the module the suite got registered in.
+    pub fn module_path(&self) -> &'static str {
+        self.module_path
+    }
+}
+
+inventory::collect!(Registration);
+
+/// Returns all registered suites' registrations.
+///
+/// Link-time collection doesn't preserve any order, so registrations get sorted by module path.
+pub fn registrations() -> Vec<&'static Registration> {
+    let mut registrations: Vec<_> = inventory::iter::<Registration>.into_iter().collect();
+    registrations.sort_by_key(|registration| registration.module_path);
+    registrations
+}
+
+/// Builds all registered suites, ordered by module path and name.
+pub fn suites() -> Vec<Box<dyn RunnableSuite>> {
+    let mut suites: Vec<_> = registrations()
+        .iter()
+        .map(|registration| registration.build())
+        .collect();
+    // Sorting is stable, so suites of the same name stay ordered by module path:
+    suites.sort_by_key(|suite| suite.header().name);
+    suites
+}
+
+/// Registers a test suite, to be discovered by [`registry::suites`](registry/fn.suites.html)
+/// (and thereby run by [`main!`](macro.main.html)).
+///
+/// Expects the path of a function returning the suite, which gets called once the suites are run.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate rspec;
+///
+/// fn suite() -> rspec::block::Suite<()> {
+///     rspec::describe("a test suite", (), |ctx| {
+///         ctx.it("passes", |_env| true);
+///     })
+/// }
+///
+/// register!(suite);
+/// #
+/// # pub fn main() {}
+/// ```
+#[macro_export]
+macro_rules! register {
+    ($suite:path) => {
+        $crate::inventory::submit! {
+            $crate::registry::Registration::new(
+                || ::std::boxed::Box::new($suite()),
+                module_path!(),
+            )
+        }
+    };
+}
+
+/// Generates a test binary's `main`, running all registered suites
+/// (see [`register!`](macro.register.html)) according to the command-line arguments
+/// (see [`cli::run_registered`](cli/fn.run_registered.html)).
+#[macro_export]
+macro_rules! main {
+    () => {
+        pub fn main() -> $crate::RunSummary {
+            $crate::cli::run_registered()
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use block::{suite, Suite};
+
+    fn first() -> Suite<()> {
+        suite("registered b", (), |ctx| {
+            ctx.it("passes", |_| {});
+        })
+    }
+
+    fn second() -> Suite<String> {
+        suite("registered a", String::new(), |ctx| {
+            ctx.it("is empty", |string| string.is_empty());
+        })
+    }
+
+    register!(first);
+    register!(second);
+
+    #[test]
+    fn it_discovers_registered_suites() {
+        let names: Vec<_> = suites()
+            .iter()
+            .map(|suite| suite.header().name)
+            .filter(|name| name.starts_with("registered"))
+            .collect();
+        assert_eq!(names, vec!["registered a", "registered b"]);
+    }
+}
@@ -1,4 +1,4 @@
-use report::{BlockReport, Report};
+use report::{BlockReport, HookFailure, Report};
 use time::Duration;
 
 /// `ContextReport` holds the results of a context's test execution.
@@ -6,25 +6,69 @@ use time::Duration;
 pub struct ContextReport {
     sub_reports: Vec<BlockReport>,
     duration: Duration,
+    #[new(default)]
+    hook_failures: Vec<HookFailure>,
 }
 
 impl ContextReport {
+    /// Adds failures of the context's hooks (or of its parent's hooks run for it) to the report.
+    pub fn with_hook_failures(mut self, hook_failures: Vec<HookFailure>) -> Self {
+        self.hook_failures.extend(hook_failures);
+        self
+    }
+
     pub fn get_blocks(&self) -> &[BlockReport] {
         &self.sub_reports[..]
     }
+
+    /// The failures of the context's own hooks, which fail the context
+    /// even if all of its examples passed.
+    pub fn get_hook_failures(&self) -> &[HookFailure] {
+        &self.hook_failures[..]
+    }
+
+    /// The number of hook failures of the context and its descendant contexts.
+    pub fn num_hook_failures(&self) -> u32 {
+        self.sub_reports.iter().fold(
+            self.hook_failures.len() as u32,
+            |count, report| match report {
+                BlockReport::Context(_, ref report) => count + report.num_hook_failures(),
+                BlockReport::Example(_, _) => count,
+            },
+        )
+    }
+
+    pub fn num_examples(&self) -> u32 {
+        self.sub_reports
+            .iter()
+            .fold(0, |count, report| count + report.num_examples())
+    }
+
+    /// Whether the context has examples, none of which have been executed,
+    /// due to either having been filtered out or not having been run.
+    pub fn is_skipped(&self) -> bool {
+        let skipped = self.get_filtered() + self.get_not_run();
+        skipped > 0 && skipped == self.num_examples()
+    }
 }
 
 impl Report for ContextReport {
     fn is_success(&self) -> bool {
-        self.sub_reports
-            .iter()
-            .fold(true, |success, report| success & report.is_success())
+        // Neither pending nor filtered examples affect a context's success:
+        self.get_failed() == 0
+            && self.get_ignored() == 0
+            && self.get_not_run() == 0
+            && self.num_hook_failures() == 0
     }
 
     fn is_failure(&self) -> bool {
-        self.sub_reports
-            .iter()
-            .fold(false, |failure, report| failure | report.is_failure())
+        // Examples only ever don't get run due to failures or the suite running out of time:
+        self.get_not_run() > 0
+            || !self.hook_failures.is_empty()
+            || self
+                .sub_reports
+                .iter()
+                .fold(false, |failure, report| failure | report.is_failure())
     }
 
     fn get_passed(&self) -> u32 {
@@ -45,6 +89,30 @@ impl Report for ContextReport {
             .fold(0, |count, report| count + report.get_ignored())
     }
 
+    fn get_pending(&self) -> u32 {
+        self.sub_reports
+            .iter()
+            .fold(0, |count, report| count + report.get_pending())
+    }
+
+    fn get_filtered(&self) -> u32 {
+        self.sub_reports
+            .iter()
+            .fold(0, |count, report| count + report.get_filtered())
+    }
+
+    fn get_not_run(&self) -> u32 {
+        self.sub_reports
+            .iter()
+            .fold(0, |count, report| count + report.get_not_run())
+    }
+
+    fn get_flaky(&self) -> u32 {
+        self.sub_reports
+            .iter()
+            .fold(0, |count, report| count + report.get_flaky())
+    }
+
     fn get_duration(&self) -> Duration {
         self.duration
     }
@@ -52,5 +120,25 @@ impl Report for ContextReport {
 
 #[cfg(test)]
 mod tests {
-    // use super::*;
+    use super::*;
+
+    use header::ExampleHeader;
+    use report::{ExampleReport, ExampleResult, HookKind};
+
+    #[test]
+    fn hook_failures_fail_the_context() {
+        let example = BlockReport::Example(
+            ExampleHeader::default(),
+            ExampleReport::new(ExampleResult::Success, Duration::zero()),
+        );
+        let failure = HookFailure::new(HookKind::AfterAll, None);
+        let inner =
+            ContextReport::new(vec![example], Duration::zero()).with_hook_failures(vec![failure]);
+        let outer = ContextReport::new(vec![BlockReport::Context(None, inner)], Duration::zero());
+        assert_eq!(outer.get_passed(), 1);
+        assert_eq!(outer.num_hook_failures(), 1);
+        assert!(outer.get_hook_failures().is_empty());
+        assert!(outer.is_failure());
+        assert!(!outer.is_success());
+    }
 }
@@ -7,20 +7,30 @@ use report::Report;
 #[cfg(feature = "expectest_compat")]
 use expectest::core::TestResult as ExpectestResult;
 
+/// The result of an example, to which new kinds of results may get added in minor releases.
 #[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
 pub enum ExampleResult {
     Success,
     Failure(Option<String>),
+    /// The example took longer than its timeout (see `Configuration.timeout`).
+    TimedOut(Duration),
     Ignored,
+    Pending(Option<String>),
+    Filtered,
+    NotRun,
 }
 
 impl ExampleResult {
-    fn is_success(&self) -> bool {
+    pub fn is_success(&self) -> bool {
         &ExampleResult::Success == self
     }
 
-    fn is_failure(&self) -> bool {
-        matches!(self, &ExampleResult::Failure(_))
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            &ExampleResult::Failure(_) | &ExampleResult::TimedOut(_)
+        )
     }
 
     fn get_passed(&self) -> u32 {
@@ -32,7 +42,7 @@ impl ExampleResult {
     }
 
     fn get_failed(&self) -> u32 {
-        if let ExampleResult::Failure(_) = self {
+        if self.is_failure() {
             1
         } else {
             0
@@ -46,6 +56,30 @@ impl ExampleResult {
             0
         }
     }
+
+    fn get_pending(&self) -> u32 {
+        if let ExampleResult::Pending(_) = self {
+            1
+        } else {
+            0
+        }
+    }
+
+    fn get_filtered(&self) -> u32 {
+        if &ExampleResult::Filtered == self {
+            1
+        } else {
+            0
+        }
+    }
+
+    fn get_not_run(&self) -> u32 {
+        if &ExampleResult::NotRun == self {
+            1
+        } else {
+            0
+        }
+    }
 }
 
 /// rspec considers examples returning `()` a success.
@@ -94,17 +128,74 @@ impl From<ExpectestResult> for ExampleResult {
     }
 }
 
+/// `ExampleAttempt` holds the result of a single execution of an example.
+#[derive(Clone, PartialEq, Eq, Debug, new)]
+pub struct ExampleAttempt {
+    result: ExampleResult,
+    duration: Duration,
+}
+
+impl ExampleAttempt {
+    pub fn get_result(&self) -> &ExampleResult {
+        &self.result
+    }
+
+    pub fn get_duration(&self) -> Duration {
+        self.duration
+    }
+}
+
 /// `ExampleReport` holds the results of a context example's test execution.
 #[derive(Clone, PartialEq, Eq, Debug, new)]
 pub struct ExampleReport {
     result: ExampleResult,
     duration: Duration,
+    #[new(default)]
+    attempts: Vec<ExampleAttempt>,
 }
 
 impl ExampleReport {
+    /// Creates a report for an example that has been executed (and possibly retried),
+    /// taking the result of its last attempt and the duration of all of them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `attempts` is empty.
+    pub fn from_attempts(attempts: Vec<ExampleAttempt>) -> Self {
+        let result = attempts
+            .last()
+            .expect("examples get attempted at least once")
+            .result
+            .clone();
+        let duration = attempts.iter().fold(Duration::zero(), |duration, attempt| {
+            duration + attempt.duration
+        });
+        ExampleReport {
+            result,
+            duration,
+            attempts,
+        }
+    }
+
     pub fn get_result(&self) -> &ExampleResult {
         &self.result
     }
+
+    /// The example's executions, in order (if created via `from_attempts`).
+    pub fn get_attempts(&self) -> &[ExampleAttempt] {
+        &self.attempts[..]
+    }
+
+    /// Whether the example only succeeded after having been retried.
+    pub fn is_flaky(&self) -> bool {
+        self.result.is_success() && self.attempts.len() > 1
+    }
+
+    /// Whether the example has not been executed,
+    /// due to either having been filtered out or not having been run.
+    pub fn is_skipped(&self) -> bool {
+        self.result.get_filtered() + self.result.get_not_run() > 0
+    }
 }
 
 impl Report for ExampleReport {
@@ -128,6 +219,26 @@ impl Report for ExampleReport {
         self.result.get_ignored()
     }
 
+    fn get_pending(&self) -> u32 {
+        self.result.get_pending()
+    }
+
+    fn get_filtered(&self) -> u32 {
+        self.result.get_filtered()
+    }
+
+    fn get_not_run(&self) -> u32 {
+        self.result.get_not_run()
+    }
+
+    fn get_flaky(&self) -> u32 {
+        if self.is_flaky() {
+            1
+        } else {
+            0
+        }
+    }
+
     fn get_duration(&self) -> Duration {
         self.duration
     }
@@ -156,6 +267,32 @@ mod tests {
         assert!(ExampleResult::from(err_result).is_failure());
     }
 
+    #[test]
+    fn timed_out() {
+        let result = ExampleResult::TimedOut(Duration::seconds(1));
+        assert!(result.is_failure());
+        assert_eq!(result.get_failed(), 1);
+    }
+
+    #[test]
+    fn from_attempts() {
+        let report = ExampleReport::from_attempts(vec![
+            ExampleAttempt::new(ExampleResult::Failure(None), Duration::seconds(1)),
+            ExampleAttempt::new(ExampleResult::Success, Duration::seconds(2)),
+        ]);
+        assert_eq!(report.get_result(), &ExampleResult::Success);
+        assert_eq!(report.get_duration(), Duration::seconds(3));
+        assert_eq!(report.get_attempts().len(), 2);
+        assert!(report.is_flaky());
+        assert_eq!(report.get_flaky(), 1);
+
+        let report = ExampleReport::from_attempts(vec![ExampleAttempt::new(
+            ExampleResult::Success,
+            Duration::seconds(1),
+        )]);
+        assert!(!report.is_flaky());
+    }
+
     #[cfg(feature = "expectest_compat")]
     #[test]
     #[should_panic]
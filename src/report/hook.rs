@@ -0,0 +1,69 @@
+use std::fmt;
+
+/// The kind of a hook (see [`Context::before_all`](../block/struct.Context.html#method.before_all) and friends).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HookKind {
+    BeforeAll,
+    BeforeEach,
+    AfterAll,
+    AfterEach,
+    AroundAll,
+    AroundEach,
+}
+
+impl fmt::Display for HookKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HookKind::BeforeAll => write!(f, "before_all"),
+            HookKind::BeforeEach => write!(f, "before_each"),
+            HookKind::AfterAll => write!(f, "after_all"),
+            HookKind::AfterEach => write!(f, "after_each"),
+            HookKind::AroundAll => write!(f, "around_all"),
+            HookKind::AroundEach => write!(f, "around_each"),
+        }
+    }
+}
+
+/// `HookFailure` holds the reason of a hook's failure.
+///
+/// Examples affected by a failing hook get reported as failed as well:
+/// all examples of a context whose `before_all` hook failed,
+/// or the example a `before_each`/`after_each` hook failed for.
+#[derive(Clone, PartialEq, Eq, Debug, new)]
+pub struct HookFailure {
+    kind: HookKind,
+    message: Option<String>,
+}
+
+impl HookFailure {
+    pub fn get_kind(&self) -> HookKind {
+        self.kind
+    }
+
+    pub fn get_message(&self) -> Option<&str> {
+        self.message.as_ref().map(|message| &message[..])
+    }
+}
+
+impl fmt::Display for HookFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} hook failed", self.kind)?;
+        if let Some(ref message) = self.message {
+            write!(f, ": {}", message)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failure_fmt() {
+        let failure = HookFailure::new(HookKind::AfterEach, Some("reason".to_owned()));
+        assert_eq!(failure.to_string(), "after_each hook failed: reason");
+        let failure = HookFailure::new(HookKind::BeforeAll, None);
+        assert_eq!(failure.to_string(), "before_all hook failed");
+    }
+}
@@ -2,12 +2,16 @@
 
 mod context;
 mod example;
+mod hook;
+mod run;
 mod suite;
 
 pub use time::Duration;
 
 pub use report::context::*;
 pub use report::example::*;
+pub use report::hook::*;
+pub use report::run::*;
 pub use report::suite::*;
 
 use header::ContextHeader;
@@ -22,6 +26,20 @@ pub trait Report {
     fn get_failed(&self) -> u32;
     fn get_ignored(&self) -> u32;
 
+    // Counts of results added after 1.0, which reports not telling them apart have none of:
+    fn get_pending(&self) -> u32 {
+        0
+    }
+    fn get_filtered(&self) -> u32 {
+        0
+    }
+    fn get_not_run(&self) -> u32 {
+        0
+    }
+    fn get_flaky(&self) -> u32 {
+        0
+    }
+
     fn get_duration(&self) -> Duration;
 }
 
@@ -39,6 +57,20 @@ impl BlockReport {
             BlockReport::Example(_, _) => None,
         }
     }
+
+    pub fn num_examples(&self) -> u32 {
+        match self {
+            BlockReport::Context(_, ref report) => report.num_examples(),
+            BlockReport::Example(_, _) => 1,
+        }
+    }
+
+    pub fn is_skipped(&self) -> bool {
+        match self {
+            BlockReport::Context(_, ref report) => report.is_skipped(),
+            BlockReport::Example(_, ref report) => report.is_skipped(),
+        }
+    }
 }
 
 impl Report for BlockReport {
@@ -77,6 +109,34 @@ impl Report for BlockReport {
         }
     }
 
+    fn get_pending(&self) -> u32 {
+        match self {
+            BlockReport::Context(_, ref report) => report.get_pending(),
+            BlockReport::Example(_, ref report) => report.get_pending(),
+        }
+    }
+
+    fn get_filtered(&self) -> u32 {
+        match self {
+            BlockReport::Context(_, ref report) => report.get_filtered(),
+            BlockReport::Example(_, ref report) => report.get_filtered(),
+        }
+    }
+
+    fn get_not_run(&self) -> u32 {
+        match self {
+            BlockReport::Context(_, ref report) => report.get_not_run(),
+            BlockReport::Example(_, ref report) => report.get_not_run(),
+        }
+    }
+
+    fn get_flaky(&self) -> u32 {
+        match self {
+            BlockReport::Context(_, ref report) => report.get_flaky(),
+            BlockReport::Example(_, ref report) => report.get_flaky(),
+        }
+    }
+
     fn get_duration(&self) -> Duration {
         match self {
             BlockReport::Context(_, ref report) => report.get_duration(),
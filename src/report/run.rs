@@ -0,0 +1,117 @@
+use time::Duration;
+
+use report::{Report, SuiteReport};
+
+/// `RunReport` holds the results of running several suites together
+/// (see [`Runner::run_all`](../runner/struct.Runner.html#method.run_all)).
+#[derive(PartialEq, Eq, Clone, Debug, new)]
+pub struct RunReport {
+    suites: Vec<SuiteReport>,
+    duration: Duration,
+}
+
+impl RunReport {
+    pub fn get_suites(&self) -> &[SuiteReport] {
+        &self.suites[..]
+    }
+
+    /// Whether any of the suites contained focused blocks, restricting their runs to those.
+    pub fn is_focused(&self) -> bool {
+        self.suites.iter().any(SuiteReport::is_focused)
+    }
+}
+
+impl Report for RunReport {
+    fn is_success(&self) -> bool {
+        self.suites.iter().all(Report::is_success)
+    }
+
+    fn is_failure(&self) -> bool {
+        self.suites.iter().any(Report::is_failure)
+    }
+
+    fn get_passed(&self) -> u32 {
+        self.suites
+            .iter()
+            .fold(0, |count, report| count + report.get_passed())
+    }
+
+    fn get_failed(&self) -> u32 {
+        self.suites
+            .iter()
+            .fold(0, |count, report| count + report.get_failed())
+    }
+
+    fn get_ignored(&self) -> u32 {
+        self.suites
+            .iter()
+            .fold(0, |count, report| count + report.get_ignored())
+    }
+
+    fn get_pending(&self) -> u32 {
+        self.suites
+            .iter()
+            .fold(0, |count, report| count + report.get_pending())
+    }
+
+    fn get_filtered(&self) -> u32 {
+        self.suites
+            .iter()
+            .fold(0, |count, report| count + report.get_filtered())
+    }
+
+    fn get_not_run(&self) -> u32 {
+        self.suites
+            .iter()
+            .fold(0, |count, report| count + report.get_not_run())
+    }
+
+    fn get_flaky(&self) -> u32 {
+        self.suites
+            .iter()
+            .fold(0, |count, report| count + report.get_flaky())
+    }
+
+    /// The run's wall-clock time, which may be less than the sum of
+    /// its suites' durations if they got run in parallel.
+    fn get_duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use header::*;
+    use report::*;
+
+    fn suite(result: ExampleResult) -> SuiteReport {
+        let context = ContextReport::new(
+            vec![BlockReport::Example(
+                ExampleHeader::default(),
+                ExampleReport::new(result, Duration::zero()),
+            )],
+            Duration::zero(),
+        );
+        SuiteReport::new(SuiteHeader::new(SuiteLabel::Suite, "suite"), context, false)
+    }
+
+    #[test]
+    fn it_aggregates_suites() {
+        let report = RunReport::new(
+            vec![
+                suite(ExampleResult::Success),
+                suite(ExampleResult::Failure(None)),
+                suite(ExampleResult::Pending(None)),
+            ],
+            Duration::seconds(1),
+        );
+        assert_eq!(report.get_passed(), 1);
+        assert_eq!(report.get_failed(), 1);
+        assert_eq!(report.get_pending(), 1);
+        assert!(report.is_failure());
+        assert!(!report.is_success());
+        assert_eq!(report.get_duration(), Duration::seconds(1));
+    }
+}
@@ -8,6 +8,7 @@ use report::{ContextReport, Report};
 pub struct SuiteReport {
     header: SuiteHeader,
     context: ContextReport,
+    focused: bool,
 }
 
 impl SuiteReport {
@@ -18,6 +19,11 @@ impl SuiteReport {
     pub fn get_context(&self) -> &ContextReport {
         &self.context
     }
+
+    /// Whether the suite contained focused blocks, restricting the run to those.
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
 }
 
 impl Report for SuiteReport {
@@ -41,6 +47,22 @@ impl Report for SuiteReport {
         self.context.get_ignored()
     }
 
+    fn get_pending(&self) -> u32 {
+        self.context.get_pending()
+    }
+
+    fn get_filtered(&self) -> u32 {
+        self.context.get_filtered()
+    }
+
+    fn get_not_run(&self) -> u32 {
+        self.context.get_not_run()
+    }
+
+    fn get_flaky(&self) -> u32 {
+        self.context.get_flaky()
+    }
+
     fn get_duration(&self) -> Duration {
         self.context.get_duration()
     }
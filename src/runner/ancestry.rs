@@ -0,0 +1,223 @@
+use std::panic::Location;
+
+use time::{Duration, Instant};
+
+use block::{Context, Mark, Suite};
+use report::HookKind;
+
+/// The suite and contexts enclosing the block being visited.
+#[derive(Clone, Default, Debug)]
+pub(crate) struct Ancestry {
+    names: Vec<&'static str>,
+    locations: Vec<&'static Location<'static>>,
+    has_focus: bool,
+    is_focused: bool,
+    is_pending: bool,
+    pending_reason: Option<String>,
+    timeout: Option<Duration>,
+    retries: Option<u32>,
+    deadline: Option<Instant>,
+    failed_hook: Option<HookKind>,
+}
+
+impl Ancestry {
+    /// Returns the ancestry of a suite's root context,
+    /// whose examples have to be done by `deadline` (if any).
+    pub fn suite<T>(&self, suite: &Suite<T>, deadline: Option<Instant>) -> Self {
+        let mut names = self.names.clone();
+        names.push(suite.header.name);
+        let mut locations = self.locations.clone();
+        locations.extend(suite.header.location);
+        Ancestry {
+            names,
+            locations,
+            has_focus: suite.is_focused(),
+            is_focused: self.is_focused,
+            is_pending: self.is_pending,
+            pending_reason: self.pending_reason.clone(),
+            timeout: self.timeout,
+            retries: self.retries,
+            deadline,
+            failed_hook: self.failed_hook,
+        }
+    }
+
+    /// Returns the ancestry of a context's children.
+    ///
+    /// Name-less contexts (see [`Context::scope`](../block/struct.Context.html#method.scope))
+    /// don't show up in the logs and thus don't show up in full names either.
+    pub fn context<T>(&self, context: &Context<T>) -> Self {
+        let mut names = self.names.clone();
+        let mut locations = self.locations.clone();
+        if let Some(ref header) = context.header {
+            names.push(header.name);
+            locations.extend(header.location);
+        }
+        // The outermost pending context is the one its examples are pending for:
+        let (is_pending, pending_reason) = match context.mark {
+            Mark::Pending(ref reason) if !self.is_pending => (true, reason.clone()),
+            _ => (self.is_pending, self.pending_reason.clone()),
+        };
+        Ancestry {
+            names,
+            locations,
+            has_focus: self.has_focus,
+            is_focused: self.is_focused || context.mark == Mark::Focused,
+            is_pending,
+            pending_reason,
+            timeout: context.timeout.or(self.timeout),
+            retries: context.retries.or(self.retries),
+            deadline: self.deadline,
+            failed_hook: self.failed_hook,
+        }
+    }
+
+    /// Returns this ancestry, within which a hook of the given kind failed,
+    /// failing all examples within it.
+    pub fn with_failed_hook(&self, kind: HookKind) -> Self {
+        Ancestry {
+            failed_hook: Some(kind),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the full name of a block named `name` within this ancestry.
+    pub fn full_name(&self, name: &str) -> String {
+        let mut full_name = String::new();
+        for ancestor in &self.names {
+            full_name.push_str(ancestor);
+            full_name.push_str(" > ");
+        }
+        full_name.push_str(name);
+        full_name
+    }
+
+    /// Returns the known locations of the suite and named contexts,
+    /// followed by `location` (if any), that of a block within this ancestry.
+    pub fn locations(
+        &self,
+        location: Option<&'static Location<'static>>,
+    ) -> Vec<&'static Location<'static>> {
+        let mut locations = self.locations.clone();
+        locations.extend(location);
+        locations
+    }
+
+    /// Whether the suite contains any focused examples or contexts.
+    pub fn has_focus(&self) -> bool {
+        self.has_focus
+    }
+
+    /// Whether any of the enclosing contexts is focused.
+    pub fn is_focused(&self) -> bool {
+        self.is_focused
+    }
+
+    /// Whether any of the enclosing contexts is pending.
+    pub fn is_pending(&self) -> bool {
+        self.is_pending
+    }
+
+    /// The reason the outermost pending enclosing context is pending for, if any.
+    pub fn pending_reason(&self) -> Option<&str> {
+        self.pending_reason.as_deref()
+    }
+
+    /// The timeout of the innermost enclosing context providing one.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// The retry count of the innermost enclosing context providing one.
+    pub fn retries(&self) -> Option<u32> {
+        self.retries
+    }
+
+    /// The point in time by which the suite's examples have to be done.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// The kind of the hook whose failure fails the examples within this ancestry, if any.
+    pub fn failed_hook(&self) -> Option<HookKind> {
+        self.failed_hook
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use block::suite;
+    use header::{ContextHeader, ContextLabel};
+
+    #[test]
+    fn full_name() {
+        let suite = suite("suite", (), |_| {});
+        let context: Context<()> =
+            Context::new(Some(ContextHeader::new(ContextLabel::Context, "context")));
+        let scope: Context<()> = Context::new(None);
+        let ancestry = Ancestry::default()
+            .suite(&suite, None)
+            .context(&context)
+            .context(&scope);
+        assert_eq!(ancestry.full_name("example"), "suite > context > example");
+        assert_eq!(Ancestry::default().full_name("example"), "example");
+    }
+
+    #[test]
+    fn locations() {
+        let suite = suite("suite", (), |_| {});
+        let context: Context<()> =
+            Context::new(Some(ContextHeader::new(ContextLabel::Context, "context")));
+        let ancestry = Ancestry::default().suite(&suite, None).context(&context);
+        let here = Location::caller();
+        assert_eq!(
+            ancestry.locations(Some(here)),
+            vec![suite.header.location.unwrap(), here]
+        );
+    }
+
+    #[test]
+    fn pending_reason() {
+        let mut outer: Context<()> = Context::new(None);
+        outer.mark = Mark::Pending(Some("outer".to_owned()));
+        let mut inner: Context<()> = Context::new(None);
+        inner.mark = Mark::Pending(Some("inner".to_owned()));
+        assert_eq!(Ancestry::default().pending_reason(), None);
+        let ancestry = Ancestry::default().context(&outer).context(&inner);
+        assert!(ancestry.is_pending());
+        assert_eq!(ancestry.pending_reason(), Some("outer"));
+    }
+
+    #[test]
+    fn timeout() {
+        let mut outer: Context<()> = Context::new(None);
+        outer.timeout(Duration::seconds(2));
+        let mut inner: Context<()> = Context::new(None);
+        inner.timeout(Duration::seconds(1));
+        let ancestry = Ancestry::default().context(&outer);
+        assert_eq!(ancestry.timeout(), Some(Duration::seconds(2)));
+        assert_eq!(
+            ancestry.context(&inner).timeout(),
+            Some(Duration::seconds(1))
+        );
+        let scope: Context<()> = Context::new(None);
+        assert_eq!(
+            ancestry.context(&scope).timeout(),
+            Some(Duration::seconds(2))
+        );
+    }
+
+    #[test]
+    fn retries() {
+        let mut outer: Context<()> = Context::new(None);
+        outer.retries(3);
+        let mut inner: Context<()> = Context::new(None);
+        inner.retries(0);
+        let ancestry = Ancestry::default().context(&outer);
+        assert_eq!(Ancestry::default().retries(), None);
+        assert_eq!(ancestry.retries(), Some(3));
+        assert_eq!(ancestry.context(&inner).retries(), Some(0));
+    }
+}
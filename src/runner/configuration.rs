@@ -1,15 +1,82 @@
 // derive_builder emits warnings otherwise:
 #![allow(unused_mut)]
 
+use time::Duration;
+
+use runner::Filter;
+
+/// Whether a Runner executes pending examples and contexts
+/// (the equivalent of libtest's `--ignored` and `--include-ignored`).
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum PendingMode {
+    /// Reports pending examples as pending, without executing them.
+    #[default]
+    Skip,
+    /// Executes pending examples, just like any other example.
+    Include,
+    /// Executes pending examples only, filtering out all others.
+    Only,
+}
+
 /// A Runner's configuration.
 #[derive(Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct Configuration {
     /// Whether the runner executes tests in parallel
     #[builder(default = "true")]
     pub parallel: bool,
-    /// Whether the runner exits the procees upon encountering failures
-    #[builder(default = "true")]
+    /// Whether the runner exits the process upon being dropped after encountering failures
+    /// (rather than leaving that to the test binary, see [`Runner::finish`](struct.Runner.html#method.finish))
+    #[builder(default = "false")]
     pub exit_on_failure: bool,
+    /// Which examples the runner executes, matched against their full name
+    /// (non-matching examples get reported as filtered out)
+    #[builder(default, setter(strip_option))]
+    pub filter: Option<Filter>,
+    /// Which examples the runner skips, matched against their full name
+    /// (matching examples get reported as filtered out)
+    #[builder(default)]
+    pub skip: Vec<Filter>,
+    /// Whether the runner executes pending examples and contexts
+    #[builder(default)]
+    pub pending_mode: PendingMode,
+    /// How many threads the runner executes tests on, if `parallel` is enabled
+    /// (defaults to one per CPU)
+    #[builder(default, setter(strip_option))]
+    pub test_threads: Option<usize>,
+    /// After how many failures the runner stops executing further examples
+    /// (reporting them as not run instead), which has to be at least one
+    #[builder(default, setter(strip_option))]
+    pub fail_fast: Option<u32>,
+    /// Whether the runner executes each context's blocks in random order
+    /// (implied by `seed`)
+    #[builder(default = "false")]
+    pub random_order: bool,
+    /// The seed making a random order reproducible, enabling `random_order`
+    /// (defaults to a fresh seed per runner, if `random_order` is enabled)
+    #[builder(default, setter(strip_option))]
+    pub seed: Option<u64>,
+    /// How long each example is allowed to take before being reported as timed out
+    /// (overridable per context, see [`Context::timeout`](../block/struct.Context.html#method.timeout)),
+    /// either right away or, for suites run with [`Runner::run`](struct.Runner.html#method.run),
+    /// only once it returned
+    #[builder(default, setter(strip_option))]
+    pub timeout: Option<Duration>,
+    /// How long the whole suite is allowed to take, after which the runner
+    /// times out the running examples and stops executing further ones
+    /// (reporting them as not run instead)
+    #[builder(default, setter(strip_option))]
+    pub suite_timeout: Option<Duration>,
+    /// How many times the runner re-executes failing examples before reporting them as failed
+    /// (overridable per context, see [`Context::retries`](../block/struct.Context.html#method.retries))
+    #[builder(default = "0")]
+    pub retries: u32,
+    /// How long an example may take before the logger flags it as slow
+    #[builder(default, setter(strip_option))]
+    pub slow_threshold: Option<Duration>,
+    /// How many of the slowest examples and contexts the logger lists at the end of its summary
+    #[builder(default = "0")]
+    pub slowest: usize,
 }
 
 impl Default for Configuration {
@@ -18,6 +85,16 @@ impl Default for Configuration {
     }
 }
 
+impl ConfigurationBuilder {
+    fn validate(&self) -> Result<(), String> {
+        // Halting after no failures would not execute any example:
+        if let Some(Some(0)) = self.fail_fast {
+            return Err("fail_fast must be at least 1".to_owned());
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -26,7 +103,19 @@ mod tests {
     fn default_with_builder() {
         let config = ConfigurationBuilder::default().build().unwrap();
         assert_eq!(config.parallel, true);
-        assert_eq!(config.exit_on_failure, true);
+        assert_eq!(config.exit_on_failure, false);
+        assert!(config.filter.is_none());
+        assert!(config.skip.is_empty());
+        assert_eq!(config.pending_mode, PendingMode::Skip);
+        assert!(config.test_threads.is_none());
+        assert!(config.fail_fast.is_none());
+        assert_eq!(config.random_order, false);
+        assert!(config.seed.is_none());
+        assert!(config.timeout.is_none());
+        assert!(config.suite_timeout.is_none());
+        assert_eq!(config.retries, 0);
+        assert!(config.slow_threshold.is_none());
+        assert_eq!(config.slowest, 0);
     }
 
     #[test]
@@ -44,20 +133,75 @@ mod tests {
     fn builder() {
         let config = ConfigurationBuilder::default().build().unwrap();
         assert_eq!(config.parallel, true);
-        assert_eq!(config.exit_on_failure, true);
+        assert_eq!(config.exit_on_failure, false);
 
         let config = ConfigurationBuilder::default()
             .parallel(false)
             .build()
             .unwrap();
         assert_eq!(config.parallel, false);
-        assert_eq!(config.exit_on_failure, true);
+        assert_eq!(config.exit_on_failure, false);
+
+        let config = ConfigurationBuilder::default()
+            .filter(Filter::substring("example"))
+            .build()
+            .unwrap();
+        assert!(config.filter.is_some());
+
+        let config = ConfigurationBuilder::default()
+            .skip(vec![Filter::substring("example")])
+            .pending_mode(PendingMode::Only)
+            .test_threads(2)
+            .build()
+            .unwrap();
+        assert_eq!(config.skip.len(), 1);
+        assert_eq!(config.pending_mode, PendingMode::Only);
+        assert_eq!(config.test_threads, Some(2));
+
+        let config = ConfigurationBuilder::default()
+            .fail_fast(1)
+            .build()
+            .unwrap();
+        assert_eq!(config.fail_fast, Some(1));
+
+        let config = ConfigurationBuilder::default().fail_fast(0).build();
+        assert_eq!(
+            config.err(),
+            Some("fail_fast must be at least 1".to_owned())
+        );
 
         let config = ConfigurationBuilder::default()
-            .exit_on_failure(false)
+            .random_order(true)
+            .seed(42)
+            .build()
+            .unwrap();
+        assert_eq!(config.random_order, true);
+        assert_eq!(config.seed, Some(42));
+
+        let config = ConfigurationBuilder::default()
+            .timeout(Duration::seconds(1))
+            .suite_timeout(Duration::minutes(1))
+            .build()
+            .unwrap();
+        assert_eq!(config.timeout, Some(Duration::seconds(1)));
+        assert_eq!(config.suite_timeout, Some(Duration::minutes(1)));
+
+        let config = ConfigurationBuilder::default().retries(3).build().unwrap();
+        assert_eq!(config.retries, 3);
+
+        let config = ConfigurationBuilder::default()
+            .slow_threshold(Duration::seconds(1))
+            .slowest(10)
+            .build()
+            .unwrap();
+        assert_eq!(config.slow_threshold, Some(Duration::seconds(1)));
+        assert_eq!(config.slowest, 10);
+
+        let config = ConfigurationBuilder::default()
+            .exit_on_failure(true)
             .build()
             .unwrap();
         assert_eq!(config.parallel, true);
-        assert_eq!(config.exit_on_failure, false);
+        assert_eq!(config.exit_on_failure, true);
     }
 }
@@ -0,0 +1,118 @@
+//! Execution of a suite's blocks, as far as it depends on what the suite's environment allows for.
+//!
+//! Executing blocks on several threads requires environments that are `Send + Sync`,
+//! which only suites run with [`Runner::run`](../struct.Runner.html#method.run)
+//! or [`Runner::run_all`](../struct.Runner.html#method.run_all) are known to have.
+//! Running examples on threads of their own, so that they can be given up on once they
+//! timed out, additionally requires environments that are `'static`, which only
+//! suites run with `Runner::run_all` (see [`RunnableSuite`](../trait.RunnableSuite.html))
+//! or [`rspec::run`](../../fn.run.html) are known to be.
+//! Suites run with [`Runner::run_serial`](../struct.Runner.html#method.run_serial)
+//! get all of their blocks executed on the current thread instead.
+
+use time::Duration;
+
+use block::{Context, Environment, ExampleFunction};
+use report::{BlockReport, ExampleResult};
+use runner::timeout::run_with_timeout;
+use runner::{Ancestry, Runner};
+
+type EvaluateBlocks<T> = fn(&Runner, &Context<T>, &T, &Ancestry, &Execution<T>) -> Vec<BlockReport>;
+
+type RunWithTimeout<T> = fn(&ExampleFunction<T>, T, Duration) -> (ExampleResult, Option<T>);
+
+/// How the blocks of a suite get executed.
+pub(crate) struct Execution<'a, T: 'a> {
+    environment: &'a Environment<T>,
+    /// The contexts being evaluated, the first one being the outermost.
+    contexts: Vec<&'a Context<T>>,
+    evaluate_blocks_parallel: Option<EvaluateBlocks<T>>,
+    run_with_timeout: Option<RunWithTimeout<T>>,
+}
+
+impl<'a, T> Execution<'a, T> {
+    /// Allows for evaluating blocks in parallel and for running examples on threads of their own.
+    pub(crate) fn threaded(environment: &'a Environment<T>) -> Self
+    where
+        T: 'static + Send + Sync + ::std::fmt::Debug,
+    {
+        Execution {
+            environment,
+            contexts: vec![],
+            evaluate_blocks_parallel: Some(Runner::evaluate_blocks_parallel::<T>),
+            run_with_timeout: Some(run_with_timeout::<T>),
+        }
+    }
+
+    /// Allows for evaluating blocks in parallel, running examples on the evaluating threads.
+    pub(crate) fn parallel(environment: &'a Environment<T>) -> Self
+    where
+        T: Send + Sync + ::std::fmt::Debug,
+    {
+        Execution {
+            environment,
+            contexts: vec![],
+            evaluate_blocks_parallel: Some(Runner::evaluate_blocks_parallel::<T>),
+            run_with_timeout: None,
+        }
+    }
+
+    /// Executes all blocks on the current thread.
+    pub(crate) fn serial(environment: &'a Environment<T>) -> Self {
+        Execution {
+            environment,
+            contexts: vec![],
+            evaluate_blocks_parallel: None,
+            run_with_timeout: None,
+        }
+    }
+
+    /// Returns the execution of a context's blocks.
+    pub(crate) fn context<'b>(&'b self, context: &'b Context<T>) -> Execution<'b, T> {
+        let mut contexts = self.contexts.clone();
+        contexts.push(context);
+        Execution {
+            environment: self.environment,
+            contexts,
+            evaluate_blocks_parallel: self.evaluate_blocks_parallel,
+            run_with_timeout: self.run_with_timeout,
+        }
+    }
+
+    /// Returns the contexts enclosing the context being evaluated whose `before_each`
+    /// and `after_each` hooks have to be called for the environments of its blocks,
+    /// the first one being the outermost.
+    ///
+    /// Cloned environments inherit the changes made by those hooks from their parent's,
+    /// built ones don't (see [`suite_with`](../../block/fn.suite_with.html)).
+    pub(crate) fn enclosing_contexts(&self) -> &[&'a Context<T>] {
+        match self.environment {
+            Environment::Cloned(..) => &[],
+            Environment::Built(_) => match self.contexts.split_last() {
+                Some((_, enclosing_contexts)) => enclosing_contexts,
+                None => &[],
+            },
+        }
+    }
+
+    /// Returns the environment of the suite's root context.
+    pub(crate) fn root(&self) -> T {
+        self.environment.root()
+    }
+
+    /// Returns the environment of a block, given the one of its parent.
+    pub(crate) fn fresh(&self, parent: &T) -> T {
+        self.environment.fresh(parent)
+    }
+
+    /// Returns a function evaluating a context's blocks in parallel, if allowed.
+    pub(crate) fn evaluate_blocks_parallel(&self) -> Option<EvaluateBlocks<T>> {
+        self.evaluate_blocks_parallel
+    }
+
+    /// Returns a function running an example on a thread of its own, if allowed
+    /// (see [`run_with_timeout`](../timeout/fn.run_with_timeout.html)).
+    pub(crate) fn run_with_timeout(&self) -> Option<RunWithTimeout<T>> {
+        self.run_with_timeout
+    }
+}
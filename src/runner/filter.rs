@@ -0,0 +1,140 @@
+//! Filters select the examples a Runner executes by their full name or their source location.
+
+use std::panic::Location;
+use std::path::Path;
+
+use regex::{Error, Regex};
+
+/// A predicate on an example's full name, that is the names of its suite,
+/// its (named) ancestor contexts and its own name, joined by `" > "`:
+///
+/// ```text
+/// a test suite > a context > an example
+/// ```
+#[derive(Clone, Debug)]
+pub enum Filter {
+    /// Selects examples whose full name contains the given string.
+    Substring(String),
+    /// Selects examples whose full name matches the given regular expression.
+    Regex(Regex),
+    /// Selects examples whose full name equals the given string.
+    Exact(String),
+    /// Selects examples declared at the given line of a file,
+    /// or within a suite or context declared there.
+    Line(String, u32),
+    /// Selects examples selected by any of the given filters.
+    Any(Vec<Filter>),
+}
+
+impl Filter {
+    /// Creates a filter selecting examples whose full name contains `pattern`.
+    pub fn substring<S>(pattern: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Filter::Substring(pattern.into())
+    }
+
+    /// Creates a filter selecting examples whose full name matches the regular expression `pattern`.
+    pub fn regex(pattern: &str) -> Result<Self, Error> {
+        Regex::new(pattern).map(Filter::Regex)
+    }
+
+    /// Creates a filter selecting the example whose full name is `name`.
+    pub fn exact<S>(name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Filter::Exact(name.into())
+    }
+
+    /// Creates a filter selecting the examples declared at `line` of `file`,
+    /// or within a suite or context declared there.
+    ///
+    /// Files match by their trailing path components, so `"specs.rs"`
+    /// matches both `tests/specs.rs` and `tests/unit/specs.rs`.
+    pub fn line<S>(file: S, line: u32) -> Self
+    where
+        S: Into<String>,
+    {
+        Filter::Line(file.into(), line)
+    }
+
+    /// Creates a filter selecting examples selected by any of `filters`.
+    pub fn any(filters: Vec<Filter>) -> Self {
+        Filter::Any(filters)
+    }
+
+    /// Whether an example of the given full name gets selected,
+    /// regardless of where it got declared.
+    pub fn is_match(&self, full_name: &str) -> bool {
+        self.is_match_at(full_name, &[])
+    }
+
+    /// Whether an example of the given full name gets selected,
+    /// given the locations of its suite, its ancestor contexts and itself (as far as known).
+    pub fn is_match_at(&self, full_name: &str, locations: &[&Location]) -> bool {
+        match self {
+            Filter::Substring(ref pattern) => full_name.contains(pattern.as_str()),
+            Filter::Regex(ref regex) => regex.is_match(full_name),
+            Filter::Exact(ref name) => full_name == name,
+            Filter::Line(ref file, line) => locations.iter().any(|location| {
+                location.line() == *line && Path::new(location.file()).ends_with(file)
+            }),
+            Filter::Any(ref filters) => filters
+                .iter()
+                .any(|filter| filter.is_match_at(full_name, locations)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substring() {
+        let filter = Filter::substring("context > an");
+        assert!(filter.is_match("a suite > a context > an example"));
+        assert!(!filter.is_match("a suite > a context > the example"));
+    }
+
+    #[test]
+    fn regex() {
+        let filter = Filter::regex(r"^a suite > .* > an example$").unwrap();
+        assert!(filter.is_match("a suite > a context > an example"));
+        assert!(!filter.is_match("a suite > a context > another example"));
+    }
+
+    #[test]
+    fn exact() {
+        let filter = Filter::exact("a suite > an example");
+        assert!(filter.is_match("a suite > an example"));
+        assert!(!filter.is_match("a suite > an example 2"));
+    }
+
+    #[test]
+    fn line() {
+        let here = Location::caller();
+        let filter = Filter::line("runner/filter.rs", here.line());
+        assert!(filter.is_match_at("an example", &[here]));
+        assert!(Filter::line("filter.rs", here.line()).is_match_at("an example", &[here]));
+        assert!(!Filter::line("ilter.rs", here.line()).is_match_at("an example", &[here]));
+        assert!(!Filter::line("filter.rs", here.line() + 1).is_match_at("an example", &[here]));
+        assert!(!filter.is_match("an example"));
+    }
+
+    #[test]
+    fn any() {
+        let filter = Filter::any(vec![Filter::exact("a"), Filter::substring("b")]);
+        assert!(filter.is_match("a"));
+        assert!(filter.is_match("abc"));
+        assert!(!filter.is_match("ac"));
+        assert!(!Filter::any(vec![]).is_match("a"));
+    }
+
+    #[test]
+    fn invalid_regex() {
+        assert!(Filter::regex("(").is_err());
+    }
+}
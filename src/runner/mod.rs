@@ -1,31 +1,53 @@
 //! Runners are responsible for executing a test suite's examples.
 
+mod ancestry;
 mod configuration;
+mod execution;
+mod filter;
 mod observer;
+pub(crate) mod panics;
+mod random;
+mod runnable;
+mod summary;
+mod timeout;
 
+pub(crate) use runner::ancestry::*;
 pub use runner::configuration::*;
+pub(crate) use runner::execution::Execution;
+pub use runner::filter::*;
 pub use runner::observer::*;
+pub(crate) use runner::random::seed_from_env;
+pub use runner::random::SEED_ENV_VAR;
+pub use runner::runnable::*;
+pub use runner::summary::*;
+
+use runner::random::Random;
 
 use std::borrow::Borrow;
 use std::cell::Cell;
 use std::ops::{Deref, DerefMut};
-use std::panic;
+use std::panic::{self, catch_unwind, AssertUnwindSafe};
 #[cfg(not(test))]
 use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
-use time::Instant;
+use time::{Duration, Instant};
 
 use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
 
 use block::Block;
-use block::Context;
 use block::Example;
+use block::Mark;
 use block::Suite;
+use block::{AroundHook, Context, Hook};
 use report::ContextReport;
-use report::ExampleReport;
+use report::RunReport;
 use report::SuiteReport;
 use report::{BlockReport, Report};
+use report::{ExampleAttempt, ExampleReport, ExampleResult};
+use report::{HookFailure, HookKind};
 use visitor::TestSuiteVisitor;
 
 /// Runner for executing a test suite's examples.
@@ -33,31 +55,260 @@ pub struct Runner {
     pub configuration: configuration::Configuration,
     observers: Vec<Arc<dyn RunnerObserver>>,
     should_exit: Mutex<Cell<bool>>,
+    summary: Mutex<RunSummary>,
+    failures: AtomicUsize,
+    selected: AtomicUsize,
+    seed: Option<u64>,
 }
 
 impl Runner {
     pub fn new(configuration: Configuration, observers: Vec<Arc<dyn RunnerObserver>>) -> Runner {
+        let seed = match configuration.seed {
+            Some(seed) => Some(seed),
+            None if configuration.random_order => Some(random::random_seed()),
+            None => None,
+        };
         Runner {
             configuration,
             observers,
             should_exit: Mutex::new(Cell::new(false)),
+            summary: Mutex::new(RunSummary::default()),
+            failures: AtomicUsize::new(0),
+            selected: AtomicUsize::new(0),
+            seed,
         }
     }
+
+    /// The seed of the runner's random ordering, if enabled.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// The number of examples the current run executes (or reports as pending or not run),
+    /// that is those not filtered out (see [`list`](#method.list)).
+    pub fn num_selected_examples(&self) -> usize {
+        self.selected.load(Ordering::SeqCst)
+    }
 }
 
 impl Runner {
+    /// Runs a suite, evaluating its blocks in parallel if the configuration asks for it.
+    ///
+    /// Examples don't get aborted once they exceed their timeout
+    /// (see [`Configuration.timeout`](struct.Configuration.html#structfield.timeout)),
+    /// as that requires running them on threads of their own and thus a `'static` environment:
+    /// they only get reported as timed out once they returned, so a hanging example hangs the run.
+    /// Suites with a `'static` environment can be run with [`run_all`](#method.run_all)
+    /// (or [`rspec::run`](../fn.run.html)) instead, giving up on examples once they timed out.
     pub fn run<T>(&self, suite: &Suite<T>) -> SuiteReport
     where
-        T: Clone + Send + Sync + ::std::fmt::Debug,
+        T: Send + Sync + ::std::fmt::Debug,
+    {
+        self.run_suite(suite, &Execution::parallel(&suite.environment))
+    }
+
+    /// Runs a suite like [`run`](#method.run), but giving up on examples once they timed out.
+    pub(crate) fn run_threaded<T>(&self, suite: &Suite<T>) -> SuiteReport
+    where
+        T: 'static + Send + Sync + ::std::fmt::Debug,
+    {
+        self.run_suite(suite, &Execution::threaded(&suite.environment))
+    }
+
+    fn run_suite<T>(&self, suite: &Suite<T>, execution: &Execution<T>) -> SuiteReport
+    where
+        T: Send + Sync + ::std::fmt::Debug,
+    {
+        self.prepare_before_run();
+        self.selected
+            .store(self.list(suite).len(), Ordering::SeqCst);
+        let report = self.install(|| self.evaluate_suite(suite, execution));
+        self.clean_after_run();
+        self.record(&report);
+        report
+    }
+
+    /// Runs a suite whose environment is neither `Send` nor `Sync` (e.g. built by a factory,
+    /// see [`suite_with`](../block/fn.suite_with.html)), executing all of its blocks
+    /// on the current thread regardless of [`Configuration.parallel`](struct.Configuration.html#structfield.parallel).
+    /// Such suites can't be run together with others (see [`RunnableSuite`](trait.RunnableSuite.html)).
+    ///
+    /// As examples can't be aborted without running them on threads of their own,
+    /// those exceeding their timeout only get reported as timed out once they returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::cell::RefCell;
+    /// # use std::io;
+    /// # use std::rc::Rc;
+    /// # use std::sync::Arc;
+    /// #
+    /// pub fn main() -> rspec::RunSummary {
+    ///     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    ///     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    ///     let runner = rspec::Runner::new(configuration, vec![logger]);
+    ///
+    ///     let log = || Rc::new(RefCell::new(vec![]));
+    ///     runner.run_serial(&rspec::describe_with("a shared log", log, |ctx| {
+    ///         ctx.before_each(|log| log.borrow_mut().push("before_each"));
+    ///         ctx.it("starts out set up", |log| log.borrow().len() == 1);
+    ///     }));
+    ///
+    ///     runner.finish()
+    /// }
+    /// ```
+    pub fn run_serial<T>(&self, suite: &Suite<T>) -> SuiteReport
+    where
+        T: ::std::fmt::Debug,
     {
-        let mut environment = suite.environment.clone();
         self.prepare_before_run();
-        let report = self.visit(suite, &mut environment);
+        self.selected
+            .store(self.list(suite).len(), Ordering::SeqCst);
+        let report = self.evaluate_suite(suite, &Execution::serial(&suite.environment));
+        self.clean_after_run();
+        self.record(&report);
+        report
+    }
+
+    /// Runs several suites, of possibly different environment types, as a single run.
+    ///
+    /// Observers get notified of the run as a whole (see [`RunnerObserver::enter_run`](trait.RunnerObserver.html#method.enter_run)),
+    /// allowing loggers to write a single summary for all of the suites.
+    /// The suites get run in parallel if the configuration asks for it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// pub fn main() -> rspec::RunSummary {
+    ///     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    ///     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    ///     let runner = rspec::Runner::new(configuration, vec![logger]);
+    ///
+    ///     let numbers = rspec::describe("a number", 42, |ctx| {
+    ///         ctx.it("is even", |number| number % 2 == 0);
+    ///     });
+    ///     let strings = rspec::describe("a string", "hello".to_owned(), |ctx| {
+    ///         ctx.it("is lowercase", |string| string.to_lowercase() == *string);
+    ///     });
+    ///     runner.run_all(&[&numbers, &strings]);
+    ///
+    ///     runner.finish()
+    /// }
+    /// ```
+    pub fn run_all(&self, suites: &[&dyn RunnableSuite]) -> RunReport {
+        let selected = suites.iter().map(|suite| suite.list(self).len()).sum();
+        self.selected.store(selected, Ordering::SeqCst);
+        self.broadcast(|handler| handler.enter_run(self));
+        self.prepare_before_run();
+        let start_time = Instant::now();
+        let reports = self.install(|| {
+            if self.configuration.parallel {
+                suites
+                    .par_iter()
+                    .map(|suite| suite.evaluate(self))
+                    .collect()
+            } else {
+                suites.iter().map(|suite| suite.evaluate(self)).collect()
+            }
+        });
+        let report = RunReport::new(reports, Instant::now() - start_time);
         self.clean_after_run();
+        self.record(&report);
+        self.broadcast(|handler| handler.exit_run(self, &report));
+        report
+    }
+
+    pub(crate) fn evaluate_suite<T>(
+        &self,
+        suite: &Suite<T>,
+        execution: &Execution<T>,
+    ) -> SuiteReport
+    where
+        T: ::std::fmt::Debug,
+    {
+        let mut environment = execution.root();
+        self.visit(suite, &mut environment, &Ancestry::default(), execution)
+    }
+
+    /// Calls `evaluate` within the runner's dedicated thread pool, if any.
+    fn install<R, F>(&self, evaluate: F) -> R
+    where
+        R: Send,
+        F: FnOnce() -> R + Send,
+    {
+        match self.thread_pool() {
+            Some(thread_pool) => thread_pool.install(evaluate),
+            None => evaluate(),
+        }
+    }
+
+    /// Records a run's outcome, for exiting upon drop and for finishing the runner.
+    fn record<R: Report>(&self, report: &R) {
         if let Ok(mut mutex_guard) = self.should_exit.lock() {
             *mutex_guard.deref_mut().get_mut() |= report.is_failure();
         }
-        report
+        if let Ok(mut summary) = self.summary.lock() {
+            summary.add(report);
+        }
+    }
+
+    /// Returns the outcome of all of the runner's runs, for the test binary to exit with.
+    ///
+    /// Unlike dropping the runner, finishing it never exits the process
+    /// (see [`Configuration.exit_on_failure`](struct.Configuration.html#structfield.exit_on_failure)).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// pub fn main() -> rspec::RunSummary {
+    ///     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    ///     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    ///     let runner = rspec::Runner::new(configuration, vec![logger]);
+    ///
+    ///     runner.run(&rspec::describe("a test suite", (), |ctx| {
+    ///         ctx.it("passes", |_env| true);
+    ///     }));
+    ///     runner.run(&rspec::describe("another test suite", (), |ctx| {
+    ///         ctx.it("passes", |_env| true);
+    ///     }));
+    ///
+    ///     runner.finish()
+    /// }
+    /// ```
+    pub fn finish(self) -> RunSummary {
+        if let Ok(mutex_guard) = self.should_exit.lock() {
+            mutex_guard.set(false);
+        }
+        self.summary
+            .lock()
+            .map(|summary| *summary)
+            .unwrap_or_default()
+    }
+
+    /// Returns a dedicated thread pool, if the configuration asks for a specific number of threads.
+    fn thread_pool(&self) -> Option<ThreadPool> {
+        if !self.configuration.parallel {
+            return None;
+        }
+        let num_threads = self.configuration.test_threads?;
+        // Falling back to rayon's global thread pool is preferable to failing the run:
+        ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .ok()
     }
 
     fn broadcast<F>(&self, mut handler: F)
@@ -69,54 +320,288 @@ impl Runner {
         }
     }
 
-    fn wrap_all<T, U, F>(&self, context: &Context<T>, environment: &mut T, wrapped_block: F) -> U
+    /// Calls `wrapped_block` within the context's `around_all` hooks
+    /// and between its `before_all` and `after_all` hooks,
+    /// returning its result along with the hooks' failures.
+    ///
+    /// If an `around_all` or `before_all` hook fails, `wrapped_block` gets called
+    /// with an ancestry failing all of its examples. The `after_all` hooks get called regardless.
+    fn wrap_all<T, U, F>(
+        &self,
+        context: &Context<T>,
+        environment: &mut T,
+        ancestry: &Ancestry,
+        wrapped_block: F,
+    ) -> (U, Vec<HookFailure>)
+    where
+        F: FnOnce(&mut T, &Ancestry) -> U,
+    {
+        self.wrap_around(
+            (HookKind::AroundAll, &context.around_all),
+            environment,
+            ancestry,
+            |environment, ancestry| {
+                self.wrap(
+                    (HookKind::BeforeAll, &context.before_all),
+                    (HookKind::AfterAll, &context.after_all),
+                    environment,
+                    ancestry,
+                    wrapped_block,
+                )
+            },
+        )
+    }
+
+    /// Calls `wrapped_block` within the context's `around_each` hooks
+    /// and between its `before_each` and `after_each` hooks,
+    /// just like [`wrap_all`](#method.wrap_all).
+    ///
+    /// As `wrapped_block` either is one of the context's examples or evaluates a nested context,
+    /// the hooks of enclosing contexts get called around those of nested contexts.
+    /// The `before_each` and `after_each` hooks of `enclosing_contexts` get called
+    /// around the context's own (see [`Execution::enclosing_contexts`](execution/struct.Execution.html#method.enclosing_contexts)).
+    fn wrap_each<T, U, F>(
+        &self,
+        enclosing_contexts: &[&Context<T>],
+        context: &Context<T>,
+        environment: &mut T,
+        ancestry: &Ancestry,
+        wrapped_block: F,
+    ) -> (U, Vec<HookFailure>)
+    where
+        F: FnOnce(&mut T, &Ancestry) -> U,
+    {
+        let (before_each, after_each) = Self::each_hooks(enclosing_contexts, context);
+        self.wrap_around(
+            (HookKind::AroundEach, &context.around_each),
+            environment,
+            ancestry,
+            |environment, ancestry| {
+                self.wrap(
+                    (HookKind::BeforeEach, before_each),
+                    (HookKind::AfterEach, after_each),
+                    environment,
+                    ancestry,
+                    wrapped_block,
+                )
+            },
+        )
+    }
+
+    /// Returns the `before_each` hooks of the contexts, from outer to inner,
+    /// and their `after_each` hooks, from inner to outer.
+    fn each_hooks<'a, T>(
+        enclosing_contexts: &[&'a Context<T>],
+        context: &'a Context<T>,
+    ) -> (Vec<&'a Hook<T>>, Vec<&'a Hook<T>>) {
+        let contexts: Vec<_> = enclosing_contexts
+            .iter()
+            .cloned()
+            .chain(Some(context))
+            .collect();
+        let before_each = contexts
+            .iter()
+            .flat_map(|&context| &context.before_each)
+            .collect();
+        let after_each = contexts
+            .iter()
+            .rev()
+            .flat_map(|&context| &context.after_each)
+            .collect();
+        (before_each, after_each)
+    }
+
+    /// Calls `wrapped_block` within the around hooks, the first one being the outermost.
+    ///
+    /// If a hook fails before calling its block (or doesn't call it at all),
+    /// `wrapped_block` gets called with an ancestry failing all of its examples instead.
+    fn wrap_around<T, U, F>(
+        &self,
+        (kind, hooks): (HookKind, &[AroundHook<T>]),
+        environment: &mut T,
+        ancestry: &Ancestry,
+        wrapped_block: F,
+    ) -> (U, Vec<HookFailure>)
     where
-        F: Fn(&mut T) -> U,
+        F: FnOnce(&mut T, &Ancestry) -> (U, Vec<HookFailure>),
     {
-        for before_function in context.before_all.iter() {
-            before_function(environment);
+        // Hooks within a failed ancestry don't get called, as its examples don't get executed:
+        let (hook, hooks) = match hooks.split_first() {
+            Some(split) if ancestry.failed_hook().is_none() => split,
+            _ => return wrapped_block(environment, ancestry),
+        };
+        let mut wrapped_block = Some(wrapped_block);
+        let mut output = None;
+        let failure = {
+            let mut block = |environment: &mut T| {
+                // Calling the block more than once doesn't execute it again:
+                if let Some(wrapped_block) = wrapped_block.take() {
+                    let hooks = (kind, hooks);
+                    // Panics of the runner itself are no failures of the hook:
+                    output = Some(catch_unwind(AssertUnwindSafe(|| {
+                        self.wrap_around(hooks, environment, ancestry, wrapped_block)
+                    })));
+                }
+            };
+            self.call_hook(kind, || hook(environment, &mut block))
+        };
+        match output {
+            Some(Ok((result, mut failures))) => {
+                failures.extend(failure);
+                (result, failures)
+            }
+            Some(Err(payload)) => panic::resume_unwind(payload),
+            None => {
+                let failure = failure.unwrap_or_else(|| {
+                    HookFailure::new(kind, Some("block not executed".to_owned()))
+                });
+                let wrapped_block = wrapped_block.expect("block got executed");
+                let (result, mut failures) =
+                    wrapped_block(environment, &ancestry.with_failed_hook(kind));
+                failures.insert(0, failure);
+                (result, failures)
+            }
         }
-        let result = wrapped_block(environment);
-        for after_function in context.after_all.iter() {
-            after_function(environment);
+    }
+
+    fn wrap<'h, T: 'h, B, A, U, F>(
+        &self,
+        before: (HookKind, B),
+        after: (HookKind, A),
+        environment: &mut T,
+        ancestry: &Ancestry,
+        wrapped_block: F,
+    ) -> (U, Vec<HookFailure>)
+    where
+        B: IntoIterator<Item = &'h Hook<T>>,
+        A: IntoIterator<Item = &'h Hook<T>>,
+        F: FnOnce(&mut T, &Ancestry) -> U,
+    {
+        let mut failures: Vec<_> = self
+            .call_before_hooks(before, environment, ancestry)
+            .into_iter()
+            .collect();
+        let result = match failures.first() {
+            Some(failure) => {
+                wrapped_block(environment, &ancestry.with_failed_hook(failure.get_kind()))
+            }
+            None => wrapped_block(environment, ancestry),
+        };
+        failures.extend(self.call_after_hooks(after, environment, ancestry));
+        (result, failures)
+    }
+
+    /// Calls the hooks until one of them fails, returning its failure.
+    fn call_before_hooks<'h, T: 'h, I>(
+        &self,
+        (kind, hooks): (HookKind, I),
+        environment: &mut T,
+        ancestry: &Ancestry,
+    ) -> Option<HookFailure>
+    where
+        I: IntoIterator<Item = &'h Hook<T>>,
+    {
+        // Hooks within a failed ancestry don't get called, as its examples don't get executed:
+        if ancestry.failed_hook().is_some() {
+            return None;
         }
-        result
+        hooks
+            .into_iter()
+            .find_map(|hook| self.call_hook(kind, || hook(environment)))
     }
 
-    fn wrap_each<T, U, F>(&self, context: &Context<T>, environment: &mut T, wrapped_block: F) -> U
+    /// Calls all of the hooks, returning their failures.
+    fn call_after_hooks<'h, T: 'h, I>(
+        &self,
+        (kind, hooks): (HookKind, I),
+        environment: &mut T,
+        ancestry: &Ancestry,
+    ) -> Vec<HookFailure>
     where
-        F: Fn(&mut T) -> U,
+        I: IntoIterator<Item = &'h Hook<T>>,
     {
-        for before_function in context.before_each.iter() {
-            before_function(environment);
+        if ancestry.failed_hook().is_some() {
+            return vec![];
         }
-        let result = wrapped_block(environment);
-        for after_function in context.after_each.iter() {
-            after_function(environment);
+        hooks
+            .into_iter()
+            .filter_map(|hook| self.call_hook(kind, || hook(environment)))
+            .collect()
+    }
+
+    /// Calls a hook, returning its failure if it failed or panicked.
+    fn call_hook<F>(&self, kind: HookKind, hook: F) -> Option<HookFailure>
+    where
+        F: FnOnce() -> ExampleResult,
+    {
+        match catch_unwind(AssertUnwindSafe(hook)) {
+            Ok(ExampleResult::Failure(message)) => Some(HookFailure::new(kind, message)),
+            Ok(_) => None,
+            Err(payload) => Some(HookFailure::new(kind, panics::message(payload))),
         }
-        result
     }
 
-    fn evaluate_blocks_parallel<T>(&self, context: &Context<T>, environment: &T) -> Vec<BlockReport>
+    fn evaluate_blocks_parallel<T>(
+        &self,
+        context: &Context<T>,
+        environment: &T,
+        ancestry: &Ancestry,
+        execution: &Execution<T>,
+    ) -> Vec<BlockReport>
     where
-        T: Clone + Send + Sync + ::std::fmt::Debug,
+        T: Send + Sync + ::std::fmt::Debug,
     {
-        context
-            .blocks
+        let reports: Vec<_> = self
+            .ordered_blocks(context, ancestry)
             .par_iter()
-            .map(|block| self.evaluate_block(block, context, environment))
-            .collect()
+            .map(|&(index, block)| {
+                let report = self.evaluate_block(block, context, environment, ancestry, execution);
+                (index, report)
+            })
+            .collect();
+        Self::declaration_order(reports)
     }
 
-    fn evaluate_blocks_serial<T>(&self, context: &Context<T>, environment: &T) -> Vec<BlockReport>
+    fn evaluate_blocks_serial<T>(
+        &self,
+        context: &Context<T>,
+        environment: &T,
+        ancestry: &Ancestry,
+        execution: &Execution<T>,
+    ) -> Vec<BlockReport>
     where
-        T: Clone + Send + Sync + ::std::fmt::Debug,
+        T: ::std::fmt::Debug,
     {
-        context
-            .blocks
+        let reports: Vec<_> = self
+            .ordered_blocks(context, ancestry)
             .iter()
-            .map(|block| self.evaluate_block(block, context, environment))
-            .collect()
+            .map(|&(index, block)| {
+                let report = self.evaluate_block(block, context, environment, ancestry, execution);
+                (index, report)
+            })
+            .collect();
+        Self::declaration_order(reports)
+    }
+
+    /// Returns the context's blocks (along with their index)
+    /// in the order they are to be executed in.
+    fn ordered_blocks<'a, T>(
+        &self,
+        context: &'a Context<T>,
+        ancestry: &Ancestry,
+    ) -> Vec<(usize, &'a Block<T>)> {
+        let mut blocks: Vec<_> = context.blocks.iter().enumerate().collect();
+        if let Some(seed) = self.seed {
+            Random::new(seed, &ancestry.full_name("")).shuffle(&mut blocks);
+        }
+        blocks
+    }
+
+    /// Returns the reports of blocks executed in random order
+    /// in the order their blocks were declared in.
+    fn declaration_order(mut reports: Vec<(usize, BlockReport)>) -> Vec<BlockReport> {
+        reports.sort_by_key(|&(index, _)| index);
+        reports.into_iter().map(|(_, report)| report).collect()
     }
 
     fn evaluate_block<T>(
@@ -124,20 +609,408 @@ impl Runner {
         block: &Block<T>,
         context: &Context<T>,
         environment: &T,
+        ancestry: &Ancestry,
+        execution: &Execution<T>,
     ) -> BlockReport
     where
-        T: Clone + Send + Sync + ::std::fmt::Debug,
+        T: ::std::fmt::Debug,
+    {
+        if self.is_skipped(block, ancestry) {
+            return self.skipped_block_report(block, ancestry);
+        }
+        if self.is_unexecuted(block, ancestry) {
+            // Pending blocks and blocks failed by a hook don't get executed,
+            // so there is no need for setting them up.
+            return self.unexecuted_block_report(block, ancestry);
+        }
+        match block {
+            Block::Example(ref example) => {
+                // Each attempt of an example gets set up from scratch:
+                let report = self.evaluate_example(example, ancestry, || {
+                    let environment = execution.fresh(environment);
+                    let (attempt, failures) =
+                        self.wrap_example(example, context, environment, ancestry, execution);
+                    // A failing hook fails the example, whatever its own result:
+                    match failures.first() {
+                        Some(failure) => {
+                            let result = ExampleResult::Failure(Some(failure.to_string()));
+                            ExampleAttempt::new(result, attempt.get_duration())
+                        }
+                        None => attempt,
+                    }
+                });
+                BlockReport::Example(example.header.clone(), report)
+            }
+            Block::Context(_) => {
+                let (report, failures) = self.wrap_each(
+                    execution.enclosing_contexts(),
+                    context,
+                    &mut execution.fresh(environment),
+                    ancestry,
+                    |environment, ancestry| self.visit(block, environment, ancestry, execution),
+                );
+                // Failures of hooks run for a context get reported along with the context's own:
+                match report {
+                    BlockReport::Context(header, report) => {
+                        BlockReport::Context(header, report.with_hook_failures(failures))
+                    }
+                    report => report,
+                }
+            }
+        }
+    }
+
+    /// Evaluates an example, attempting it up to as many times as there are retries configured.
+    fn evaluate_example<T, F>(
+        &self,
+        example: &Example<T>,
+        ancestry: &Ancestry,
+        mut attempt: F,
+    ) -> ExampleReport
+    where
+        F: FnMut() -> ExampleAttempt,
     {
-        let mut environment = environment.clone();
-        self.wrap_each(context, &mut environment, |environment| {
-            self.visit(block, environment)
+        self.broadcast(|handler| handler.enter_example(self, &example.header));
+        let report = if let Some(result) = self.pending_result(example, ancestry) {
+            ExampleReport::new(result, Duration::zero())
+        } else if let Some(result) = self.hook_failure_result(ancestry) {
+            ExampleReport::new(result, Duration::zero())
+        } else {
+            let retries = ancestry.retries().unwrap_or(self.configuration.retries);
+            let mut attempts = vec![];
+            loop {
+                let attempt = attempt();
+                let is_failure = attempt.get_result().is_failure();
+                attempts.push(attempt);
+                if !is_failure || attempts.len() > retries as usize || self.is_halted(ancestry) {
+                    break;
+                }
+            }
+            ExampleReport::from_attempts(attempts)
+        };
+        if report.is_failure() {
+            self.failures.fetch_add(1, Ordering::SeqCst);
+        }
+        self.broadcast(|handler| handler.exit_example(self, &example.header, &report));
+        report
+    }
+
+    /// Attempts an example within its context's `around_each` hooks and between
+    /// its `before_each` and `after_each` hooks, just like [`wrap_each`](#method.wrap_each).
+    ///
+    /// If allowed, examples that can time out get run on threads of their own,
+    /// unless they are wrapped by `around_each` hooks, which need to hand them their environment.
+    /// As an example that timed out keeps its environment, its `after_each` hooks don't get called.
+    fn wrap_example<T>(
+        &self,
+        example: &Example<T>,
+        context: &Context<T>,
+        mut environment: T,
+        ancestry: &Ancestry,
+        execution: &Execution<T>,
+    ) -> (ExampleAttempt, Vec<HookFailure>) {
+        let (timeout, run_with_timeout) =
+            match (self.timeout(ancestry), execution.run_with_timeout()) {
+                (Some(timeout), Some(run_with_timeout)) if context.around_each.is_empty() => {
+                    (timeout, run_with_timeout)
+                }
+                _ => {
+                    return self.wrap_each(
+                        execution.enclosing_contexts(),
+                        context,
+                        &mut environment,
+                        ancestry,
+                        |environment, ancestry| {
+                            self.attempt_example(example, environment, ancestry)
+                        },
+                    )
+                }
+            };
+        let (before_each, after_each) = Self::each_hooks(execution.enclosing_contexts(), context);
+        let mut failures: Vec<_> = self
+            .call_before_hooks(
+                (HookKind::BeforeEach, before_each),
+                &mut environment,
+                ancestry,
+            )
+            .into_iter()
+            .collect();
+        let (attempt, environment) = match failures.first() {
+            Some(failure) => {
+                let ancestry = ancestry.with_failed_hook(failure.get_kind());
+                let attempt = self.attempt_example(example, &mut environment, &ancestry);
+                (attempt, Some(environment))
+            }
+            None => {
+                let start_time = Instant::now();
+                let (result, environment) =
+                    run_with_timeout(&example.function, environment, timeout);
+                let attempt = ExampleAttempt::new(result, Instant::now() - start_time);
+                (attempt, environment)
+            }
+        };
+        if let Some(mut environment) = environment {
+            let after_each = (HookKind::AfterEach, after_each);
+            failures.extend(self.call_after_hooks(after_each, &mut environment, ancestry));
+        }
+        (attempt, failures)
+    }
+
+    /// Attempts an example on the current thread.
+    fn attempt_example<T>(
+        &self,
+        example: &Example<T>,
+        environment: &mut T,
+        ancestry: &Ancestry,
+    ) -> ExampleAttempt {
+        if let Some(result) = self.hook_failure_result(ancestry) {
+            return ExampleAttempt::new(result, Duration::zero());
+        }
+        let start_time = Instant::now();
+        let result = (example.function)(environment);
+        let end_time = Instant::now();
+        let elapsed_time = end_time - start_time;
+        // Examples not run on threads of their own can only time out once they returned:
+        let result = match self.timeout(ancestry) {
+            Some(timeout) if elapsed_time > timeout => ExampleResult::TimedOut(timeout),
+            _ => result,
+        };
+        ExampleAttempt::new(result, elapsed_time)
+    }
+
+    /// Returns the result of an example, if it (or any of its enclosing contexts) is pending.
+    fn pending_result<T>(
+        &self,
+        example: &Example<T>,
+        ancestry: &Ancestry,
+    ) -> Option<ExampleResult> {
+        if self.configuration.pending_mode != PendingMode::Skip {
+            return None;
+        }
+        match example.mark {
+            Mark::Pending(ref reason) => Some(ExampleResult::Pending(reason.clone())),
+            _ if ancestry.is_pending() => {
+                let reason = ancestry.pending_reason().map(str::to_owned);
+                Some(ExampleResult::Pending(reason))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the result of an example, if a hook of any of its enclosing contexts failed.
+    fn hook_failure_result(&self, ancestry: &Ancestry) -> Option<ExampleResult> {
+        ancestry.failed_hook().map(|kind| {
+            let failure = HookFailure::new(kind, None);
+            ExampleResult::Failure(Some(failure.to_string()))
+        })
+    }
+
+    /// Whether a context (or any of its enclosing contexts) is pending
+    /// and thus doesn't get executed.
+    fn is_pending_context(&self, ancestry: &Ancestry) -> bool {
+        ancestry.is_pending() && self.configuration.pending_mode == PendingMode::Skip
+    }
+
+    /// Whether an example is focused (if the suite has focus),
+    /// matches the configured filter (if any) and isn't skipped.
+    fn is_selected<T>(&self, example: &Example<T>, ancestry: &Ancestry) -> bool {
+        if ancestry.has_focus() && !(ancestry.is_focused() || example.mark == Mark::Focused) {
+            return false;
+        }
+        if self.configuration.pending_mode == PendingMode::Only {
+            let is_pending = ancestry.is_pending() || matches!(example.mark, Mark::Pending(_));
+            if !is_pending {
+                return false;
+            }
+        }
+        let full_name = example.header.full_name();
+        let locations = ancestry.locations(example.header.location);
+        let is_match = match self.configuration.filter {
+            Some(ref filter) => filter.is_match_at(full_name, &locations),
+            None => true,
+        };
+        is_match
+            && !self
+                .configuration
+                .skip
+                .iter()
+                .any(|filter| filter.is_match_at(full_name, &locations))
+    }
+
+    /// Returns the full names of the suite's examples that would get executed, in declaration order.
+    pub fn list<T>(&self, suite: &Suite<T>) -> Vec<String> {
+        let mut names = vec![];
+        let ancestry = Ancestry::default().suite(suite, None);
+        self.list_context(
+            &suite.context,
+            &ancestry.context(&suite.context),
+            &mut names,
+        );
+        names
+    }
+
+    fn list_context<T>(&self, context: &Context<T>, ancestry: &Ancestry, names: &mut Vec<String>) {
+        for block in &context.blocks {
+            match block {
+                Block::Example(ref example) => {
+                    if self.is_selected(example, ancestry) {
+                        names.push(example.header.full_name().to_owned());
+                    }
+                }
+                Block::Context(ref context) => {
+                    self.list_context(context, &ancestry.context(context), names);
+                }
+            }
+        }
+    }
+
+    /// Whether the runner has stopped executing examples,
+    /// due to either `configuration.fail_fast` or `configuration.suite_timeout`.
+    fn is_halted(&self, ancestry: &Ancestry) -> bool {
+        let has_failed_fast = match self.configuration.fail_fast {
+            Some(max_failures) => self.failures.load(Ordering::SeqCst) >= max_failures as usize,
+            None => false,
+        };
+        let has_timed_out = match ancestry.deadline() {
+            Some(deadline) => Instant::now() >= deadline,
+            None => false,
+        };
+        has_failed_fast || has_timed_out
+    }
+
+    /// Returns how long an example is allowed to take, if limited
+    /// by either its (or the configuration's) timeout or the suite's deadline.
+    fn timeout(&self, ancestry: &Ancestry) -> Option<Duration> {
+        let timeout = ancestry.timeout().or(self.configuration.timeout);
+        let remaining = ancestry
+            .deadline()
+            .map(|deadline| ::std::cmp::max(deadline - Instant::now(), Duration::zero()));
+        match (timeout, remaining) {
+            (Some(timeout), Some(remaining)) => Some(::std::cmp::min(timeout, remaining)),
+            (timeout, remaining) => timeout.or(remaining),
+        }
+    }
+
+    /// Whether a context contains examples, none of which have been selected.
+    fn is_filtered_out<T>(&self, context: &Context<T>, ancestry: &Ancestry) -> bool {
+        context.num_examples() > 0 && !self.has_selected_examples(context, ancestry)
+    }
+
+    fn has_selected_examples<T>(&self, context: &Context<T>, ancestry: &Ancestry) -> bool {
+        context.blocks.iter().any(|block| match block {
+            Block::Example(ref example) => self.is_selected(example, ancestry),
+            Block::Context(ref context) => {
+                self.has_selected_examples(context, &ancestry.context(context))
+            }
         })
     }
 
+    /// Whether a block doesn't get executed, as it's filtered out or the run halted.
+    fn is_skipped<T>(&self, block: &Block<T>, ancestry: &Ancestry) -> bool {
+        let is_filtered_out = match block {
+            Block::Example(ref example) => !self.is_selected(example, ancestry),
+            Block::Context(ref context) => {
+                self.is_filtered_out(context, &ancestry.context(context))
+            }
+        };
+        is_filtered_out || self.is_halted(ancestry)
+    }
+
+    /// Returns a report for a block that doesn't get executed,
+    /// marking its examples as either filtered out or not run.
+    fn skipped_block_report<T>(&self, block: &Block<T>, ancestry: &Ancestry) -> BlockReport {
+        match block {
+            Block::Example(ref example) => {
+                let result = if self.is_selected(example, ancestry) {
+                    ExampleResult::NotRun
+                } else {
+                    ExampleResult::Filtered
+                };
+                let report = ExampleReport::new(result, Duration::zero());
+                BlockReport::Example(example.header.clone(), report)
+            }
+            Block::Context(ref context) => BlockReport::Context(
+                context.header.clone(),
+                self.skipped_context_report(context, &ancestry.context(context)),
+            ),
+        }
+    }
+
+    fn skipped_context_report<T>(
+        &self,
+        context: &Context<T>,
+        ancestry: &Ancestry,
+    ) -> ContextReport {
+        let reports = context
+            .blocks
+            .iter()
+            .map(|block| self.skipped_block_report(block, ancestry))
+            .collect();
+        ContextReport::new(reports, Duration::zero())
+    }
+
+    /// Whether a block doesn't get executed, as it's pending or a hook failed it,
+    /// yet gets reported to observers like an executed one.
+    fn is_unexecuted<T>(&self, block: &Block<T>, ancestry: &Ancestry) -> bool {
+        let is_pending = match block {
+            Block::Example(ref example) => self.pending_result(example, ancestry).is_some(),
+            Block::Context(ref context) => self.is_pending_context(&ancestry.context(context)),
+        };
+        is_pending || ancestry.failed_hook().is_some()
+    }
+
+    /// Returns a report for a block that doesn't get executed (see `is_unexecuted`),
+    /// visiting it without an environment, so that none gets built for it.
+    fn unexecuted_block_report<T>(&self, block: &Block<T>, ancestry: &Ancestry) -> BlockReport {
+        match block {
+            Block::Example(ref example) => {
+                let report = self.evaluate_example(example, ancestry, || {
+                    unreachable!("pending examples and examples failed by a hook aren't attempted")
+                });
+                BlockReport::Example(example.header.clone(), report)
+            }
+            Block::Context(ref context) => BlockReport::Context(
+                context.header.clone(),
+                self.unexecuted_context_report(context, &ancestry.context(context)),
+            ),
+        }
+    }
+
+    fn unexecuted_context_report<T>(
+        &self,
+        context: &Context<T>,
+        ancestry: &Ancestry,
+    ) -> ContextReport {
+        if let Some(ref header) = context.header {
+            self.broadcast(|handler| handler.enter_context(self, header));
+        }
+        let start_time = Instant::now();
+        let reports = self
+            .ordered_blocks(context, ancestry)
+            .iter()
+            .map(|&(index, block)| {
+                let report = if self.is_skipped(block, ancestry) {
+                    self.skipped_block_report(block, ancestry)
+                } else {
+                    self.unexecuted_block_report(block, ancestry)
+                };
+                (index, report)
+            })
+            .collect();
+        let reports = Self::declaration_order(reports);
+        let report = ContextReport::new(reports, Instant::now() - start_time);
+        if let Some(ref header) = context.header {
+            self.broadcast(|handler| handler.exit_context(self, header, &report));
+        }
+        report
+    }
+
     fn prepare_before_run(&self) {
-        panic::set_hook(Box::new(|_panic_info| {
-            // XXX panics already catched at the test call site, don't output the trace in stdout
-        }));
+        // Each run stops after its own failures (see `configuration.fail_fast`):
+        self.failures.store(0, Ordering::SeqCst);
+        // Panics get caught at the test call site, so their details get recorded
+        // for the failure's message rather than written to stderr:
+        panics::install_hook();
     }
 
     fn clean_after_run(&self) {
@@ -163,6 +1036,9 @@ impl Drop for Runner {
         };
 
         if self.configuration.exit_on_failure && should_exit {
+            // Observers may write what they collected upon being dropped (e.g. `JUnitLogger`),
+            // which exiting the process would prevent:
+            self.observers.clear();
             // XXX Cargo test failure returns 101.
             //
             // > "We use 101 as the standard failure exit code because it's something unique
@@ -180,16 +1056,28 @@ impl Drop for Runner {
 
 impl<T> TestSuiteVisitor<Suite<T>> for Runner
 where
-    T: Clone + Send + Sync + ::std::fmt::Debug,
+    T: ::std::fmt::Debug,
 {
     type Environment = T;
     type Output = SuiteReport;
 
-    fn visit(&self, suite: &Suite<T>, environment: &mut Self::Environment) -> Self::Output {
+    fn visit(
+        &self,
+        suite: &Suite<T>,
+        environment: &mut Self::Environment,
+        ancestry: &Ancestry,
+        execution: &Execution<Self::Environment>,
+    ) -> Self::Output {
         self.broadcast(|handler| handler.enter_suite(self, &suite.header));
+        let deadline = self
+            .configuration
+            .suite_timeout
+            .map(|suite_timeout| Instant::now() + suite_timeout);
+        let ancestry = ancestry.suite(suite, deadline);
         let report = SuiteReport::new(
             suite.header.clone(),
-            self.visit(&suite.context, environment),
+            self.visit(&suite.context, environment, &ancestry, execution),
+            ancestry.has_focus(),
         );
         self.broadcast(|handler| handler.exit_suite(self, &suite.header, &report));
         report
@@ -198,21 +1086,27 @@ where
 
 impl<T> TestSuiteVisitor<Block<T>> for Runner
 where
-    T: Clone + Send + Sync + ::std::fmt::Debug,
+    T: ::std::fmt::Debug,
 {
     type Environment = T;
     type Output = BlockReport;
 
-    fn visit(&self, member: &Block<T>, environment: &mut Self::Environment) -> Self::Output {
+    fn visit(
+        &self,
+        member: &Block<T>,
+        environment: &mut Self::Environment,
+        ancestry: &Ancestry,
+        execution: &Execution<Self::Environment>,
+    ) -> Self::Output {
         match member {
             Block::Example(ref example) => {
                 let header = example.header.clone();
-                let report = self.visit(example, environment);
+                let report = self.visit(example, environment, ancestry, execution);
                 BlockReport::Example(header, report)
             }
             Block::Context(ref context) => {
                 let header = context.header.clone();
-                let report = self.visit(context, &mut environment.clone());
+                let report = self.visit(context, environment, ancestry, execution);
                 BlockReport::Context(header, report)
             }
         }
@@ -221,28 +1115,49 @@ where
 
 impl<T> TestSuiteVisitor<Context<T>> for Runner
 where
-    T: Clone + Send + Sync + ::std::fmt::Debug,
+    T: ::std::fmt::Debug,
 {
     type Environment = T;
     type Output = ContextReport;
 
-    fn visit(&self, context: &Context<T>, environment: &mut Self::Environment) -> Self::Output {
+    fn visit(
+        &self,
+        context: &Context<T>,
+        environment: &mut Self::Environment,
+        ancestry: &Ancestry,
+        execution: &Execution<Self::Environment>,
+    ) -> Self::Output {
+        let ancestry = ancestry.context(context);
+        if self.is_filtered_out(context, &ancestry) || self.is_halted(&ancestry) {
+            return self.skipped_context_report(context, &ancestry);
+        }
+        let execution = &execution.context(context);
         if let Some(ref header) = context.header {
-            self.broadcast(|handler| handler.enter_context(self, &header));
+            self.broadcast(|handler| handler.enter_context(self, header));
         }
         let start_time = Instant::now();
-        let reports: Vec<_> = self.wrap_all(context, environment, |environment| {
-            if self.configuration.parallel {
-                self.evaluate_blocks_parallel(context, environment)
-            } else {
-                self.evaluate_blocks_serial(context, environment)
-            }
-        });
+        let (reports, failures) = if self.is_pending_context(&ancestry) {
+            // Pending contexts don't get executed, so there is no need for setting them up.
+            let reports = self.evaluate_blocks_serial(context, environment, &ancestry, execution);
+            (reports, vec![])
+        } else {
+            self.wrap_all(
+                context,
+                environment,
+                &ancestry,
+                |environment, ancestry| match execution.evaluate_blocks_parallel() {
+                    Some(evaluate_blocks_parallel) if self.configuration.parallel => {
+                        evaluate_blocks_parallel(self, context, environment, ancestry, execution)
+                    }
+                    _ => self.evaluate_blocks_serial(context, environment, ancestry, execution),
+                },
+            )
+        };
         let end_time = Instant::now();
         let elapsed_time = end_time - start_time;
-        let report = ContextReport::new(reports, elapsed_time);
+        let report = ContextReport::new(reports, elapsed_time).with_hook_failures(failures);
         if let Some(ref header) = context.header {
-            self.broadcast(|handler| handler.exit_context(self, &header, &report));
+            self.broadcast(|handler| handler.exit_context(self, header, &report));
         }
         report
     }
@@ -250,20 +1165,21 @@ where
 
 impl<T> TestSuiteVisitor<Example<T>> for Runner
 where
-    T: Clone + Send + Sync + ::std::fmt::Debug,
+    T: ::std::fmt::Debug,
 {
     type Environment = T;
     type Output = ExampleReport;
 
-    fn visit(&self, example: &Example<T>, environment: &mut Self::Environment) -> Self::Output {
-        self.broadcast(|handler| handler.enter_example(self, &example.header));
-        let start_time = Instant::now();
-        let result = (example.function)(environment);
-        let end_time = Instant::now();
-        let elapsed_time = end_time - start_time;
-        let report = ExampleReport::new(result, elapsed_time);
-        self.broadcast(|handler| handler.exit_example(self, &example.header, &report));
-        report
+    fn visit(
+        &self,
+        example: &Example<T>,
+        environment: &mut Self::Environment,
+        ancestry: &Ancestry,
+        _execution: &Execution<Self::Environment>,
+    ) -> Self::Output {
+        self.evaluate_example(example, ancestry, || {
+            self.attempt_example(example, environment, ancestry)
+        })
     }
 }
 
@@ -347,7 +1263,7 @@ mod tests {
                 runner.broadcast(|observer| observer.enter_suite(&runner, &expected.clone()));
                 // assert
                 let lock = spy1.events.lock().expect("no dangling threads");
-                let res = (*lock).get(0).expect("to have been called once");
+                let res = (*lock).first().expect("to have been called once");
                 assert_eq!(&("enter_suite", expected), res);
             }
         }
@@ -362,7 +1278,13 @@ mod tests {
                 // arrange
                 let runner = Runner::default();
                 // act
-                runner.wrap_each(&Context::default(), &mut (), |_| {})
+                runner.wrap_each(
+                    &[],
+                    &Context::default(),
+                    &mut (),
+                    &Ancestry::default(),
+                    |_, _| {},
+                );
                 // assert
             }
 
@@ -372,9 +1294,13 @@ mod tests {
                 let runner = Runner::default();
                 let has_been_called = AtomicBool::new(false);
                 // act
-                runner.wrap_each(&Context::default(), &mut (), |_| {
-                    has_been_called.store(true, Ordering::SeqCst)
-                });
+                runner.wrap_each(
+                    &[],
+                    &Context::default(),
+                    &mut (),
+                    &Ancestry::default(),
+                    |_, _| has_been_called.store(true, Ordering::SeqCst),
+                );
                 // assert
                 assert_eq!(true, has_been_called.load(Ordering::SeqCst));
             }
@@ -388,7 +1314,7 @@ mod tests {
                 let mut context = Context::default();
                 // act
                 context.before_each(move |_| closure_bool_handler.store(true, Ordering::SeqCst));
-                runner.wrap_each(&context, &mut (), |_| ());
+                runner.wrap_each(&[], &context, &mut (), &Ancestry::default(), |_, _| ());
                 // assert
                 assert_eq!(true, has_been_called.load(Ordering::SeqCst));
             }
@@ -402,7 +1328,7 @@ mod tests {
                 let mut context = Context::default();
                 // act
                 context.after_each(move |_| closure_bool_handler.store(true, Ordering::SeqCst));
-                runner.wrap_each(&context, &mut (), |_| ());
+                runner.wrap_each(&[], &context, &mut (), &Ancestry::default(), |_, _| ());
                 // assert
                 assert_eq!(true, has_been_called.load(Ordering::SeqCst));
             }
@@ -422,7 +1348,7 @@ mod tests {
                 context.before_each(move |_| {
                     closure_counter_handler2.fetch_add(1, Ordering::SeqCst);
                 });
-                runner.wrap_each(&context, &mut (), |_| ());
+                runner.wrap_each(&[], &context, &mut (), &Ancestry::default(), |_, _| ());
                 // assert
                 assert_eq!(2, call_counter.load(Ordering::SeqCst));
             }
@@ -442,7 +1368,7 @@ mod tests {
                 context.after_each(move |_| {
                     closure_counter_handler2.fetch_add(1, Ordering::SeqCst);
                 });
-                runner.wrap_each(&context, &mut (), |_| ());
+                runner.wrap_each(&[], &context, &mut (), &Ancestry::default(), |_, _| ());
                 // assert
                 assert_eq!(2, call_counter.load(Ordering::SeqCst));
             }
@@ -459,7 +1385,7 @@ mod tests {
                 context.before_each(move |_| {
                     last_caller_handler1.store(1, Ordering::SeqCst);
                 });
-                runner.wrap_each(&context, &mut (), |_| {
+                runner.wrap_each(&[], &context, &mut (), &Ancestry::default(), |_, _| {
                     last_caller_handler2.store(2, Ordering::SeqCst);
                 });
                 // assert
@@ -478,25 +1404,106 @@ mod tests {
                 context.after_each(move |_| {
                     last_caller_handler1.store(1, Ordering::SeqCst);
                 });
-                runner.wrap_each(&context, &mut (), |_| {
+                runner.wrap_each(&[], &context, &mut (), &Ancestry::default(), |_, _| {
                     last_caller_handler2.store(2, Ordering::SeqCst);
                 });
                 // assert
                 assert_eq!(1, last_caller_id.load(Ordering::SeqCst));
             }
-        }
-
-        mod wrap_all {
-            use super::*;
-
-            use std::sync::atomic::*;
+
+            #[test]
+            fn it_calls_around_each_hooks_around_the_other_hooks() {
+                // arrange
+                let runner = Runner::default();
+                let events = Arc::new(Mutex::new(vec![]));
+                let (e1, e2, e3, e4, e5) = (
+                    events.clone(),
+                    events.clone(),
+                    events.clone(),
+                    events.clone(),
+                    events.clone(),
+                );
+                let mut context = Context::default();
+                // act
+                context.before_each(move |_| e1.lock().unwrap().push("before"));
+                context.after_each(move |_| e2.lock().unwrap().push("after"));
+                context.around_each(move |env, block| {
+                    e3.lock().unwrap().push("outer enter");
+                    block(env);
+                    e3.lock().unwrap().push("outer exit");
+                });
+                context.around_each(move |env, block| {
+                    e4.lock().unwrap().push("inner enter");
+                    block(env);
+                    block(env);
+                    e4.lock().unwrap().push("inner exit");
+                });
+                let (_, failures) =
+                    runner.wrap_each(&[], &context, &mut (), &Ancestry::default(), |_, _| {
+                        e5.lock().unwrap().push("block");
+                    });
+                // assert
+                assert!(failures.is_empty());
+                assert_eq!(
+                    vec![
+                        "outer enter",
+                        "inner enter",
+                        "before",
+                        "block",
+                        "after",
+                        "inner exit",
+                        "outer exit"
+                    ],
+                    *events.lock().unwrap()
+                );
+            }
+
+            #[test]
+            fn it_fails_the_closure_if_an_around_each_hook_does_not_call_its_block() {
+                // arrange
+                let runner = Runner::default();
+                let has_been_called = Arc::new(AtomicBool::new(false));
+                let closure_bool_handler = has_been_called.clone();
+                let mut context = Context::default();
+                // act
+                context.around_each(|_, _| {});
+                context.before_each(move |_| closure_bool_handler.store(true, Ordering::SeqCst));
+                let (failed_hook, failures) = runner.wrap_each(
+                    &[],
+                    &context,
+                    &mut (),
+                    &Ancestry::default(),
+                    |_, ancestry| ancestry.failed_hook(),
+                );
+                // assert
+                assert_eq!(false, has_been_called.load(Ordering::SeqCst));
+                assert_eq!(Some(HookKind::AroundEach), failed_hook);
+                assert_eq!(
+                    vec![HookFailure::new(
+                        HookKind::AroundEach,
+                        Some("block not executed".to_owned())
+                    )],
+                    failures
+                );
+            }
+        }
+
+        mod wrap_all {
+            use super::*;
+
+            use std::sync::atomic::*;
 
             #[test]
             fn it_can_be_called() {
                 // arrange
                 let runner = Runner::default();
                 // act
-                runner.wrap_all(&Context::default(), &mut (), |_| {})
+                runner.wrap_all(
+                    &Context::default(),
+                    &mut (),
+                    &Ancestry::default(),
+                    |_, _| {},
+                );
                 // assert
             }
 
@@ -506,9 +1513,12 @@ mod tests {
                 let runner = Runner::default();
                 let has_been_called = AtomicBool::new(false);
                 // act
-                runner.wrap_all(&Context::default(), &mut (), |_| {
-                    has_been_called.store(true, Ordering::SeqCst)
-                });
+                runner.wrap_all(
+                    &Context::default(),
+                    &mut (),
+                    &Ancestry::default(),
+                    |_, _| has_been_called.store(true, Ordering::SeqCst),
+                );
                 // assert
                 assert_eq!(true, has_been_called.load(Ordering::SeqCst));
             }
@@ -522,7 +1532,7 @@ mod tests {
                 let mut context = Context::default();
                 // act
                 context.before_all(move |_| closure_bool_handler.store(true, Ordering::SeqCst));
-                runner.wrap_all(&context, &mut (), |_| ());
+                runner.wrap_all(&context, &mut (), &Ancestry::default(), |_, _| ());
                 // assert
                 assert_eq!(true, has_been_called.load(Ordering::SeqCst));
             }
@@ -536,7 +1546,7 @@ mod tests {
                 let mut context = Context::default();
                 // act
                 context.after_all(move |_| closure_bool_handler.store(true, Ordering::SeqCst));
-                runner.wrap_all(&context, &mut (), |_| ());
+                runner.wrap_all(&context, &mut (), &Ancestry::default(), |_, _| ());
                 // assert
                 assert_eq!(true, has_been_called.load(Ordering::SeqCst));
             }
@@ -556,7 +1566,7 @@ mod tests {
                 context.before_all(move |_| {
                     closure_counter_handler2.fetch_add(1, Ordering::SeqCst);
                 });
-                runner.wrap_all(&context, &mut (), |_| ());
+                runner.wrap_all(&context, &mut (), &Ancestry::default(), |_, _| ());
                 // assert
                 assert_eq!(2, call_counter.load(Ordering::SeqCst));
             }
@@ -576,7 +1586,7 @@ mod tests {
                 context.after_all(move |_| {
                     closure_counter_handler2.fetch_add(1, Ordering::SeqCst);
                 });
-                runner.wrap_all(&context, &mut (), |_| ());
+                runner.wrap_all(&context, &mut (), &Ancestry::default(), |_, _| ());
                 // assert
                 assert_eq!(2, call_counter.load(Ordering::SeqCst));
             }
@@ -593,7 +1603,7 @@ mod tests {
                 context.before_all(move |_| {
                     last_caller_handler1.store(1, Ordering::SeqCst);
                 });
-                runner.wrap_all(&context, &mut (), |_| {
+                runner.wrap_all(&context, &mut (), &Ancestry::default(), |_, _| {
                     last_caller_handler2.store(2, Ordering::SeqCst);
                 });
                 // assert
@@ -612,7 +1622,7 @@ mod tests {
                 context.after_all(move |_| {
                     last_caller_handler1.store(1, Ordering::SeqCst);
                 });
-                runner.wrap_all(&context, &mut (), |_| {
+                runner.wrap_all(&context, &mut (), &Ancestry::default(), |_, _| {
                     last_caller_handler2.store(2, Ordering::SeqCst);
                 });
                 // assert
@@ -621,9 +1631,107 @@ mod tests {
         }
     }
 
+    mod run_all {
+        use super::*;
+
+        use block::suite;
+        use header::SuiteHeader;
+
+        #[derive(Default)]
+        struct SpyObserver {
+            runs: AtomicUsize,
+            suites: AtomicUsize,
+        }
+
+        impl RunnerObserver for SpyObserver {
+            fn exit_run(&self, _runner: &Runner, _report: &RunReport) {
+                self.runs.fetch_add(1, Ordering::SeqCst);
+            }
+
+            fn exit_suite(&self, _runner: &Runner, _header: &SuiteHeader, _report: &SuiteReport) {
+                self.suites.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        fn run_all(parallel: bool) {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(parallel)
+                .build()
+                .unwrap();
+            let spy = Arc::new(SpyObserver::default());
+            let runner = Runner::new(config, vec![spy.clone()]);
+            let numbers = suite("numbers", 42, |ctx| {
+                ctx.it("is even", |number| number % 2 == 0);
+            });
+            let strings = suite("strings", "hello".to_owned(), |ctx| {
+                ctx.it("is empty", |string| string.is_empty());
+                ctx.it("is lowercase", |string| string.to_lowercase() == *string);
+            });
+            // act
+            let report = runner.run_all(&[&numbers, &strings]);
+            // assert
+            let names: Vec<_> = report
+                .get_suites()
+                .iter()
+                .map(|suite| suite.get_header().name)
+                .collect();
+            assert_eq!(names, vec!["numbers", "strings"]);
+            assert_eq!(report.get_passed(), 2);
+            assert_eq!(report.get_failed(), 1);
+            assert_eq!(spy.runs.load(Ordering::SeqCst), 1);
+            assert_eq!(spy.suites.load(Ordering::SeqCst), 2);
+            assert_eq!(runner.finish().get_failed(), 1);
+        }
+
+        #[test]
+        fn it_runs_suites_of_different_types_serially() {
+            run_all(false);
+        }
+
+        #[test]
+        fn it_runs_suites_of_different_types_in_parallel() {
+            run_all(true);
+        }
+    }
+
+    mod finish {
+        use super::*;
+
+        use block::suite;
+
+        #[test]
+        fn it_sums_up_all_runs_without_exiting() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .exit_on_failure(true)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            runner.run(&suite("suite", (), |ctx| {
+                ctx.it("passes", |_| {});
+            }));
+            runner.run(&suite("suite", (), |ctx| {
+                ctx.it("fails", |_| false);
+            }));
+            // act
+            let summary = runner.finish();
+            // assert
+            assert_eq!(summary.get_passed(), 1);
+            assert_eq!(summary.get_failed(), 1);
+            assert!(summary.is_failure());
+        }
+    }
+
     mod impl_drop_for_runner {
         use super::*;
 
+        #[test]
+        fn it_should_not_abort_by_default() {
+            let runner = Runner::default();
+            (*runner.should_exit.lock().unwrap()).set(true);
+        }
+
         #[test]
         #[should_panic]
         fn it_should_abort() {
@@ -645,6 +1753,7 @@ mod tests {
     mod impl_visitor_example_for_runner {
         use super::*;
 
+        use block::Environment;
         use header::*;
         use report::*;
         use std::sync::atomic::*;
@@ -676,7 +1785,9 @@ mod tests {
             let example = Example::fixture_success();
             // act
             // assert
-            runner.visit(&example, &mut ());
+            let environment = Environment::cloned(());
+            let execution = Execution::serial(&environment);
+            runner.visit(&example, &mut (), &Ancestry::default(), &execution);
         }
 
         #[test]
@@ -686,7 +1797,9 @@ mod tests {
             let runner = Runner::new(Configuration::default(), vec![spy.clone()]);
             let example = Example::fixture_success();
             // act
-            runner.visit(&example, &mut ());
+            let environment = Environment::cloned(());
+            let execution = Execution::serial(&environment);
+            runner.visit(&example, &mut (), &Ancestry::default(), &execution);
             // assert
             assert_eq!(true, spy.enter_example.load(Ordering::SeqCst));
             assert_eq!(true, spy.exit_example.load(Ordering::SeqCst))
@@ -698,19 +1811,1327 @@ mod tests {
             let runner = Runner::default();
             let mut environment = Arc::new(AtomicBool::new(false));
             // act
-            let example = Example::new(ExampleHeader::default(), |env: &Arc<AtomicBool>| {
+            let example = Example::new(ExampleHeader::default(), |env: &mut Arc<AtomicBool>| {
                 env.store(true, Ordering::SeqCst);
                 ExampleResult::Success
             });
-            runner.visit(&example, &mut environment);
+            let source = Environment::cloned(environment.clone());
+            let execution = Execution::serial(&source);
+            runner.visit(&example, &mut environment, &Ancestry::default(), &execution);
             // assert
             assert_eq!(true, environment.load(Ordering::SeqCst));
         }
     }
 
-    mod impl_visitor_block_for_runner {
+    mod filter {
+        use super::*;
+
+        use block::suite;
+        use std::sync::atomic::*;
+
+        fn runner(filter: Filter) -> Runner {
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .filter(filter)
+                .build()
+                .unwrap();
+            Runner::new(config, vec![])
+        }
+
+        #[test]
+        fn it_only_runs_matching_examples() {
+            // arrange
+            let runner = runner(Filter::substring("suite > context > selected"));
+            let counter = Arc::new(AtomicUsize::new(0));
+            let (counter1, counter2) = (counter.clone(), counter.clone());
+            let suite = suite("suite", (), move |ctx| {
+                ctx.context("context", move |ctx| {
+                    ctx.it("selected", move |_| {
+                        counter1.fetch_add(1, Ordering::SeqCst);
+                    });
+                    ctx.it("skipped", move |_| {
+                        counter2.fetch_add(10, Ordering::SeqCst);
+                    });
+                });
+            });
+            // act
+            let report = runner.run(&suite);
+            // assert
+            assert_eq!(1, counter.load(Ordering::SeqCst));
+            assert_eq!(1, report.get_passed());
+            assert_eq!(1, report.get_filtered());
+            assert!(report.is_success());
+        }
+
+        #[test]
+        fn it_skips_hooks_of_filtered_out_contexts() {
+            // arrange
+            let runner = runner(Filter::regex("^suite > selected$").unwrap());
+            let has_been_called = Arc::new(AtomicBool::new(false));
+            let closure_bool_handler = has_been_called.clone();
+            let suite = suite("suite", (), move |ctx| {
+                ctx.it("selected", |_| {});
+                ctx.context("context", move |ctx| {
+                    ctx.before_all(move |_| closure_bool_handler.store(true, Ordering::SeqCst));
+                    ctx.it("selected", |_| {});
+                });
+            });
+            // act
+            let report = runner.run(&suite);
+            // assert
+            assert_eq!(false, has_been_called.load(Ordering::SeqCst));
+            assert_eq!(1, report.get_passed());
+            assert_eq!(1, report.get_filtered());
+        }
+
+        #[test]
+        fn it_runs_examples_declared_at_the_given_line() {
+            // arrange
+            let (mut example_line, mut context_line) = (0, 0);
+            let suite = suite("suite", (), |ctx| {
+                ctx.it("not selected", |_| false);
+                example_line = line!() + 1;
+                ctx.it("selected by line", |_| {});
+                context_line = line!() + 1;
+                ctx.context("selected by context", |ctx| {
+                    ctx.it("a", |_| {});
+                    ctx.it("b", |_| {});
+                });
+            });
+            // act
+            let examples = runner(Filter::line("runner/mod.rs", example_line)).run(&suite);
+            let contexts = runner(Filter::line("runner/mod.rs", context_line)).run(&suite);
+            // assert
+            assert_eq!(1, examples.get_passed());
+            assert_eq!(3, examples.get_filtered());
+            assert_eq!(2, contexts.get_passed());
+            assert_eq!(2, contexts.get_filtered());
+        }
+    }
+
+    mod hooks {
+        use super::*;
+
+        use block::suite;
+
+        fn runner(parallel: bool) -> Runner {
+            let config = ConfigurationBuilder::default()
+                .parallel(parallel)
+                .build()
+                .unwrap();
+            Runner::new(config, vec![])
+        }
+
+        fn context_report(report: &SuiteReport, index: usize) -> &ContextReport {
+            match report.get_context().get_blocks()[index] {
+                BlockReport::Context(_, ref report) => report,
+                _ => panic!("expected a context"),
+            }
+        }
+
+        fn example_result(report: &ContextReport, index: usize) -> &ExampleResult {
+            match report.get_blocks()[index] {
+                BlockReport::Example(_, ref report) => report.get_result(),
+                _ => panic!("expected an example"),
+            }
+        }
+
+        #[test]
+        fn it_fails_all_examples_of_a_context_whose_before_all_hook_panicked() {
+            // arrange
+            let counter = Arc::new(AtomicUsize::new(0));
+            let (counter1, counter2, counter3) =
+                (counter.clone(), counter.clone(), counter.clone());
+            let suite = suite("suite", (), move |ctx| {
+                ctx.context("context", move |ctx| {
+                    ctx.before_all(|_| panic!("boom"));
+                    ctx.after_all(move |_| {
+                        counter1.fetch_add(1, Ordering::SeqCst);
+                    });
+                    ctx.it("a", move |_| {
+                        counter2.fetch_add(10, Ordering::SeqCst);
+                    });
+                    ctx.context("nested", move |ctx| {
+                        ctx.it("b", move |_| {
+                            counter3.fetch_add(10, Ordering::SeqCst);
+                        });
+                    });
+                });
+                ctx.it("c", |_| {});
+            });
+            // act
+            let report = runner(true).run(&suite);
+            // assert
+            assert_eq!(1, counter.load(Ordering::SeqCst));
+            assert_eq!(1, report.get_passed());
+            assert_eq!(2, report.get_failed());
+            let context = context_report(&report, 0);
+            let failures = context.get_hook_failures();
+            assert_eq!(1, failures.len());
+            assert_eq!(HookKind::BeforeAll, failures[0].get_kind());
+            assert!(failures[0].get_message().unwrap().contains("'boom'"));
+            assert_eq!(
+                &ExampleResult::Failure(Some("before_all hook failed".to_owned())),
+                example_result(context, 0)
+            );
+        }
+
+        #[test]
+        fn it_fails_the_example_an_after_each_hook_panicked_after() {
+            // arrange
+            let suite = suite("suite", (), |ctx| {
+                ctx.context("context", |ctx| {
+                    ctx.after_each(|_| panic!("boom"));
+                    ctx.it("passes", |_| {});
+                });
+            });
+            // act
+            let report = runner(false).run(&suite);
+            // assert
+            assert_eq!(0, report.get_passed());
+            assert_eq!(1, report.get_failed());
+            match example_result(context_report(&report, 0), 0) {
+                ExampleResult::Failure(Some(ref message)) => {
+                    assert!(message.starts_with("after_each hook failed: thread panicked at 'boom'"))
+                }
+                result => panic!("expected a failure, got {:?}", result),
+            }
+        }
+
+        #[test]
+        fn it_reports_after_all_hook_failures_separately() {
+            // arrange
+            let suite = suite("suite", (), |ctx| {
+                ctx.after_all(|_| panic!("boom"));
+                ctx.it("passes", |_| {});
+            });
+            // act
+            let report = runner(false).run(&suite);
+            // assert
+            assert_eq!(1, report.get_passed());
+            assert_eq!(0, report.get_failed());
+            assert!(report.is_failure());
+            let failures = report.get_context().get_hook_failures();
+            assert_eq!(1, failures.len());
+            assert_eq!(HookKind::AfterAll, failures[0].get_kind());
+        }
+
+        #[test]
+        fn it_calls_around_hooks_from_outer_to_inner_contexts() {
+            // arrange
+            let events = Arc::new(Mutex::new(vec![]));
+            let (e1, e2, e3) = (events.clone(), events.clone(), events.clone());
+            let suite = suite("suite", (), move |ctx| {
+                ctx.around_all(move |env, block| {
+                    e1.lock().unwrap().push("around_all");
+                    block(env);
+                });
+                ctx.around_each(move |env, block| {
+                    e2.lock().unwrap().push("outer");
+                    block(env);
+                });
+                ctx.context("context", move |ctx| {
+                    let e4 = e3.clone();
+                    ctx.around_each(move |env, block| {
+                        e3.lock().unwrap().push("inner");
+                        block(env);
+                    });
+                    ctx.it("passes", move |_| {
+                        e4.lock().unwrap().push("example");
+                    });
+                });
+            });
+            // act
+            let report = runner(true).run(&suite);
+            // assert
+            assert_eq!(1, report.get_passed());
+            assert_eq!(
+                vec!["around_all", "outer", "inner", "example"],
+                *events.lock().unwrap()
+            );
+        }
+
+        #[test]
+        fn it_fails_the_example_an_around_each_hook_panicked_around() {
+            // arrange
+            let suite = suite("suite", (), |ctx| {
+                ctx.context("context", |ctx| {
+                    ctx.around_each(|env, block| -> () {
+                        block(env);
+                        panic!("boom")
+                    });
+                    ctx.it("passes", |_| {});
+                });
+            });
+            // act
+            let report = runner(false).run(&suite);
+            // assert
+            assert_eq!(0, report.get_passed());
+            assert_eq!(1, report.get_failed());
+            match example_result(context_report(&report, 0), 0) {
+                ExampleResult::Failure(Some(ref message)) => {
+                    assert!(
+                        message.starts_with("around_each hook failed: thread panicked at 'boom'")
+                    )
+                }
+                result => panic!("expected a failure, got {:?}", result),
+            }
+        }
+
+        #[test]
+        fn it_reports_the_error_of_a_before_all_hook_returning_err() {
+            // arrange
+            let suite = suite("suite", (), |ctx| {
+                ctx.context("context", |ctx| {
+                    ctx.try_before_all(|_| Err::<(), _>("no database"));
+                    ctx.it("passes", |_| {});
+                });
+            });
+            // act
+            let report = runner(false).run(&suite);
+            // assert
+            assert_eq!(0, report.get_passed());
+            assert_eq!(1, report.get_failed());
+            let context = context_report(&report, 0);
+            let failures = context.get_hook_failures();
+            assert_eq!(1, failures.len());
+            assert_eq!(HookKind::BeforeAll, failures[0].get_kind());
+            assert_eq!(Some("\"no database\""), failures[0].get_message());
+        }
+
+        #[test]
+        fn it_fails_the_example_a_before_each_hook_returned_false_for() {
+            // arrange
+            let suite = suite("suite", (), |ctx| {
+                ctx.context("context", |ctx| {
+                    ctx.try_before_each(|_| false);
+                    ctx.it("passes", |_| {});
+                });
+                ctx.it("also passes", |_| {});
+            });
+            // act
+            let report = runner(false).run(&suite);
+            // assert
+            assert_eq!(1, report.get_passed());
+            assert_eq!(1, report.get_failed());
+            match example_result(context_report(&report, 0), 0) {
+                ExampleResult::Failure(Some(ref message)) => {
+                    assert!(message.starts_with("before_each hook failed: assertion failed"))
+                }
+                result => panic!("expected a failure, got {:?}", result),
+            }
+        }
+    }
+
+    mod skip {
+        use super::*;
+
+        use block::suite;
+
+        #[test]
+        fn it_skips_matching_examples() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .skip(vec![Filter::substring("skipped")])
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let suite = suite("suite", (), |ctx| {
+                ctx.it("selected", |_| {});
+                ctx.it("skipped", |_| false);
+            });
+            // act
+            let report = runner.run(&suite);
+            // assert
+            assert_eq!(1, report.get_passed());
+            assert_eq!(1, report.get_filtered());
+        }
+    }
+
+    mod list {
+        use super::*;
+
+        use block::suite;
+
+        #[test]
+        fn it_lists_selected_examples_without_running_them() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .filter(Filter::substring("selected"))
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let suite = suite("suite", (), |ctx| {
+                ctx.it("selected", |_| -> () { panic!("should not run") });
+                ctx.scope(|ctx| {
+                    ctx.context("context", |ctx| {
+                        ctx.it("selected", |_| {});
+                        ctx.it("other", |_| {});
+                    });
+                });
+            });
+            // act
+            let names = runner.list(&suite);
+            // assert
+            assert_eq!(
+                names,
+                vec!["suite > selected", "suite > context > selected"]
+            );
+        }
+
+        #[test]
+        fn it_numbers_examples_with_the_same_name() {
+            // arrange
+            let config = ConfigurationBuilder::default().build().unwrap();
+            let runner = Runner::new(config, vec![]);
+            let suite = suite("suite", (), |ctx| {
+                ctx.it("example", |_| {});
+                ctx.scope(|ctx| {
+                    ctx.it("example", |_| {});
+                });
+                ctx.context("context", |ctx| {
+                    ctx.it("example", |_| {});
+                });
+                ctx.it("example", |_| {});
+            });
+            // act
+            let names = runner.list(&suite);
+            // assert
+            assert_eq!(
+                names,
+                vec![
+                    "suite > example",
+                    "suite > example (2)",
+                    "suite > context > example",
+                    "suite > example (3)",
+                ]
+            );
+        }
+
+        #[test]
+        fn it_skips_numbers_taken_by_declared_names() {
+            // arrange
+            let config = ConfigurationBuilder::default().build().unwrap();
+            let runner = Runner::new(config, vec![]);
+            let suite = suite("suite", (), |ctx| {
+                ctx.it("x", |_| {});
+                ctx.it("x", |_| {});
+                ctx.it("x (2)", |_| {});
+                ctx.it("x", |_| {});
+            });
+            // act
+            let names = runner.list(&suite);
+            // assert
+            assert_eq!(
+                names,
+                vec![
+                    "suite > x",
+                    "suite > x (3)",
+                    "suite > x (2)",
+                    "suite > x (4)"
+                ]
+            );
+        }
+
+        #[test]
+        fn it_runs_exactly_one_listed_example_with_its_ancestors_hooks() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .filter(Filter::exact("suite > context > example (2)"))
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let events = Arc::new(Mutex::new(vec![]));
+            let (e1, e2, e3, e4, e5) = (
+                events.clone(),
+                events.clone(),
+                events.clone(),
+                events.clone(),
+                events.clone(),
+            );
+            let suite = suite("suite", (), move |ctx| {
+                ctx.before_all(move |_| e1.lock().unwrap().push("before_all"));
+                ctx.context("context", move |ctx| {
+                    ctx.before_each(move |_| e2.lock().unwrap().push("before_each"));
+                    ctx.it("example", move |_| e3.lock().unwrap().push("first"));
+                    ctx.it("example", move |_| e4.lock().unwrap().push("second"));
+                });
+                ctx.it("example (2)", move |_| e5.lock().unwrap().push("other"));
+            });
+            // act
+            let report = runner.run(&suite);
+            // assert
+            assert_eq!(
+                *events.lock().unwrap(),
+                vec!["before_all", "before_each", "second"]
+            );
+            assert_eq!(report.get_passed(), 1);
+            assert_eq!(report.get_filtered(), 2);
+        }
+    }
+
+    mod focus {
         use super::*;
 
+        use block::suite;
+
+        #[test]
+        fn it_only_runs_focused_blocks() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let counter = Arc::new(AtomicUsize::new(0));
+            let (counter1, counter2, counter3) =
+                (counter.clone(), counter.clone(), counter.clone());
+            let suite = suite("suite", (), move |ctx| {
+                ctx.fcontext("focused context", move |ctx| {
+                    ctx.it("example", move |_| {
+                        counter1.fetch_add(1, Ordering::SeqCst);
+                    });
+                });
+                ctx.context("context", move |ctx| {
+                    ctx.fit("focused example", move |_| {
+                        counter2.fetch_add(1, Ordering::SeqCst);
+                    });
+                    ctx.it("example", move |_| {
+                        counter3.fetch_add(10, Ordering::SeqCst);
+                    });
+                });
+            });
+            // act
+            let report = runner.run(&suite);
+            // assert
+            assert_eq!(2, counter.load(Ordering::SeqCst));
+            assert_eq!(2, report.get_passed());
+            assert_eq!(1, report.get_filtered());
+            assert!(report.is_focused());
+        }
+    }
+
+    mod pending {
+        use super::*;
+
+        use block::suite;
+
+        #[test]
+        fn it_reports_pending_blocks_without_executing_them() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let counter = Arc::new(AtomicUsize::new(0));
+            let (counter1, counter2, counter3) =
+                (counter.clone(), counter.clone(), counter.clone());
+            let suite = suite("suite", (), move |ctx| {
+                ctx.xcontext("pending context", "not yet supported", move |ctx| {
+                    ctx.before_all(move |_| {
+                        counter1.fetch_add(1, Ordering::SeqCst);
+                    });
+                    ctx.it("example", move |_| {
+                        counter2.fetch_add(1, Ordering::SeqCst);
+                    });
+                });
+                ctx.xit("pending example", "broken", move |_| {
+                    counter3.fetch_add(1, Ordering::SeqCst);
+                });
+                ctx.pending("pending example", "reason");
+                ctx.todo("pending example");
+                ctx.it("example", |_| {});
+            });
+            // act
+            let report = runner.run(&suite);
+            // assert
+            assert_eq!(0, counter.load(Ordering::SeqCst));
+            assert_eq!(1, report.get_passed());
+            assert_eq!(4, report.get_pending());
+            assert!(report.is_success());
+            let blocks = report.get_context().get_blocks();
+            let context_example = match blocks[0] {
+                BlockReport::Context(_, ref report) => &report.get_blocks()[0],
+                _ => panic!("expected a context"),
+            };
+            for &(block, reason) in &[
+                (context_example, "not yet supported"),
+                (&blocks[1], "broken"),
+            ] {
+                match *block {
+                    BlockReport::Example(_, ref report) => assert_eq!(
+                        &ExampleResult::Pending(Some(reason.to_owned())),
+                        report.get_result()
+                    ),
+                    _ => panic!("expected an example"),
+                }
+            }
+        }
+    }
+
+    mod pending_mode {
+        use super::*;
+
+        use block::suite;
+
+        fn run(pending_mode: PendingMode) -> (usize, SuiteReport) {
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .pending_mode(pending_mode)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let counter = Arc::new(AtomicUsize::new(0));
+            let (counter1, counter2, counter3) =
+                (counter.clone(), counter.clone(), counter.clone());
+            let suite = suite("suite", (), move |ctx| {
+                ctx.xcontext("pending context", "not yet supported", move |ctx| {
+                    ctx.before_all(move |_| {
+                        counter1.fetch_add(1, Ordering::SeqCst);
+                    });
+                    ctx.it("example", |_| {});
+                });
+                ctx.xit("pending example", "broken", move |_| {
+                    counter2.fetch_add(1, Ordering::SeqCst);
+                });
+                ctx.it("example", move |_| {
+                    counter3.fetch_add(10, Ordering::SeqCst);
+                });
+            });
+            let report = runner.run(&suite);
+            (counter.load(Ordering::SeqCst), report)
+        }
+
+        #[test]
+        fn it_includes_pending_blocks() {
+            // act
+            let (counter, report) = run(PendingMode::Include);
+            // assert
+            assert_eq!(12, counter);
+            assert_eq!(3, report.get_passed());
+            assert_eq!(0, report.get_pending());
+        }
+
+        #[test]
+        fn it_only_runs_pending_blocks() {
+            // act
+            let (counter, report) = run(PendingMode::Only);
+            // assert
+            assert_eq!(2, counter);
+            assert_eq!(2, report.get_passed());
+            assert_eq!(1, report.get_filtered());
+        }
+    }
+
+    mod test_threads {
+        use super::*;
+
+        use block::suite;
+
+        #[test]
+        fn it_runs_on_the_given_number_of_threads() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .exit_on_failure(false)
+                .test_threads(3)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let num_threads = Arc::new(AtomicUsize::new(0));
+            let num_threads_handler = num_threads.clone();
+            let suite = suite("suite", (), move |ctx| {
+                ctx.it("example", move |_| {
+                    num_threads_handler.store(rayon::current_num_threads(), Ordering::SeqCst);
+                });
+            });
+            // act
+            runner.run(&suite);
+            // assert
+            assert_eq!(3, num_threads.load(Ordering::SeqCst));
+        }
+    }
+
+    mod fail_fast {
+        use super::*;
+
+        use block::suite;
+
+        fn runner(parallel: bool, fail_fast: u32) -> Runner {
+            let config = ConfigurationBuilder::default()
+                .parallel(parallel)
+                .exit_on_failure(false)
+                .fail_fast(fail_fast)
+                .build()
+                .unwrap();
+            Runner::new(config, vec![])
+        }
+
+        fn fixture() -> Suite<()> {
+            suite("suite", (), |ctx| {
+                ctx.it("passes", |_| true);
+                ctx.it("fails", |_| false);
+                ctx.context("context", |ctx| {
+                    ctx.it("fails", |_| false);
+                    ctx.it("passes", |_| true);
+                });
+                ctx.it("fails", |_| false);
+            })
+        }
+
+        #[test]
+        fn it_stops_after_the_first_failure() {
+            // arrange
+            let runner = runner(false, 1);
+            // act
+            let report = runner.run(&fixture());
+            // assert
+            assert_eq!(1, report.get_passed());
+            assert_eq!(1, report.get_failed());
+            assert_eq!(3, report.get_not_run());
+        }
+
+        #[test]
+        fn it_stops_after_n_failures() {
+            // arrange
+            let runner = runner(false, 2);
+            // act
+            let report = runner.run(&fixture());
+            // assert
+            assert_eq!(1, report.get_passed());
+            assert_eq!(2, report.get_failed());
+            assert_eq!(2, report.get_not_run());
+        }
+
+        #[test]
+        fn it_stops_in_parallel() {
+            // arrange
+            let runner = runner(true, 1);
+            // act
+            let report = runner.run(&fixture());
+            // assert
+            assert!(report.get_failed() >= 1);
+            assert_eq!(
+                5,
+                report.get_passed() + report.get_failed() + report.get_not_run()
+            );
+        }
+
+        #[test]
+        fn it_counts_failures_per_run() {
+            // arrange
+            let runner = runner(false, 1);
+            let passing = suite("passing", (), |ctx| {
+                ctx.it("passes", |_| true);
+            });
+            // act
+            runner.run(&fixture());
+            let report = runner.run(&passing);
+            // assert
+            assert_eq!(1, report.get_passed());
+            assert_eq!(0, report.get_not_run());
+        }
+    }
+
+    mod random_order {
+        use super::*;
+
+        use block::suite;
+
+        fn run(seed: Option<u64>) -> Vec<usize> {
+            let mut builder = ConfigurationBuilder::default();
+            builder.parallel(false).exit_on_failure(false);
+            if let Some(seed) = seed {
+                builder.random_order(true).seed(seed);
+            } else {
+                builder.random_order(false);
+            }
+            let runner = Runner::new(builder.build().unwrap(), vec![]);
+            let order = Arc::new(Mutex::new(vec![]));
+            let suite = suite("suite", (), |ctx| {
+                for index in 0..10 {
+                    let order = order.clone();
+                    ctx.it("example", move |_| order.lock().unwrap().push(index));
+                }
+            });
+            runner.run(&suite);
+            let order = order.lock().unwrap().clone();
+            order
+        }
+
+        #[test]
+        fn it_preserves_the_declaration_order_by_default() {
+            assert_eq!(run(None), (0..10).collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn it_shuffles_reproducibly() {
+            let order = run(Some(42));
+            assert_ne!(order, (0..10).collect::<Vec<_>>());
+            assert_eq!(order, run(Some(42)));
+        }
+
+        #[test]
+        fn it_reports_blocks_in_declaration_order() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .random_order(true)
+                .seed(42)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let names = ["a", "b", "c", "d", "e", "f", "g", "h"];
+            let suite = suite("suite", (), |ctx| {
+                for &name in names.iter() {
+                    ctx.it(name, |_| {});
+                }
+            });
+            // act
+            let report = runner.run(&suite);
+            // assert
+            let reported: Vec<_> = report
+                .get_context()
+                .get_blocks()
+                .iter()
+                .map(|block| match block {
+                    BlockReport::Example(ref header, _) => header.name,
+                    _ => panic!("expected an example"),
+                })
+                .collect();
+            assert_eq!(reported, names);
+        }
+
+        #[test]
+        fn it_exposes_the_seed() {
+            let config = ConfigurationBuilder::default()
+                .random_order(true)
+                .seed(42)
+                .build()
+                .unwrap();
+            assert_eq!(Runner::new(config, vec![]).seed(), Some(42));
+            let config = ConfigurationBuilder::default()
+                .random_order(true)
+                .build()
+                .unwrap();
+            assert!(Runner::new(config, vec![]).seed().is_some());
+        }
+
+        #[test]
+        fn it_orders_randomly_given_a_seed() {
+            let config = ConfigurationBuilder::default().seed(42).build().unwrap();
+            assert_eq!(Runner::new(config, vec![]).seed(), Some(42));
+            let config = ConfigurationBuilder::default().build().unwrap();
+            assert_eq!(Runner::new(config, vec![]).seed(), None);
+        }
+    }
+
+    mod timeout {
+        use super::*;
+
+        use block::{suite, suite_with};
+        use std::thread;
+        use std::time::Duration as StdDuration;
+
+        fn sleep(milliseconds: u64) {
+            thread::sleep(StdDuration::from_millis(milliseconds));
+        }
+
+        /// Runs a suite through `run_all`, which gives up on examples once they timed out.
+        fn run_all(runner: &Runner, suite: &Suite<()>) -> SuiteReport {
+            runner.run_all(&[suite]).get_suites()[0].clone()
+        }
+
+        #[test]
+        fn it_times_out_slow_examples() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .timeout(Duration::milliseconds(50))
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let suite = suite("suite", (), |ctx| {
+                ctx.it("is fast", |_| {});
+                ctx.it("hangs", |_| sleep(10_000));
+            });
+            // act
+            let report = run_all(&runner, &suite);
+            // assert
+            assert_eq!(1, report.get_passed());
+            assert_eq!(1, report.get_failed());
+            let blocks = report.get_context().get_blocks();
+            match blocks[1] {
+                BlockReport::Example(_, ref report) => assert_eq!(
+                    &ExampleResult::TimedOut(Duration::milliseconds(50)),
+                    report.get_result()
+                ),
+                _ => panic!("expected an example"),
+            }
+        }
+
+        #[test]
+        fn it_gives_up_on_examples_of_suites_run_threaded() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let suite = suite("suite", (), |ctx| {
+                ctx.timeout(Duration::milliseconds(10));
+                ctx.it("hangs", |_| sleep(10_000));
+            });
+            // act
+            let start_time = Instant::now();
+            let report = runner.run_threaded(&suite);
+            // assert
+            assert_eq!(1, report.get_failed());
+            assert!(Instant::now() - start_time < Duration::seconds(5));
+        }
+
+        #[test]
+        fn it_lets_contexts_override_the_timeout() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .timeout(Duration::milliseconds(10))
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let suite = suite("suite", (), |ctx| {
+                ctx.scope(|ctx| {
+                    ctx.timeout(Duration::seconds(10));
+                    ctx.it("is slow", |_| sleep(50));
+                });
+                ctx.it("hangs", |_| sleep(10_000));
+            });
+            // act
+            let report = run_all(&runner, &suite);
+            // assert
+            assert_eq!(1, report.get_passed());
+            assert_eq!(1, report.get_failed());
+        }
+
+        #[test]
+        fn it_stops_once_the_suite_timed_out() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .suite_timeout(Duration::milliseconds(50))
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let suite = suite("suite", (), |ctx| {
+                ctx.it("is fast", |_| {});
+                ctx.it("hangs", |_| sleep(10_000));
+                ctx.it("is fast", |_| {});
+            });
+            // act
+            let report = run_all(&runner, &suite);
+            // assert
+            assert_eq!(1, report.get_passed());
+            assert_eq!(1, report.get_failed());
+            assert_eq!(1, report.get_not_run());
+            assert!(report.is_failure());
+        }
+
+        #[test]
+        fn it_skips_the_after_each_hooks_of_timed_out_examples() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .timeout(Duration::milliseconds(10))
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let (builds, after_each_calls) =
+                (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+            let factory = {
+                let builds = builds.clone();
+                move || builds.fetch_add(1, Ordering::SeqCst)
+            };
+            let suite = suite_with("suite", factory, |ctx| {
+                let after_each_calls = after_each_calls.clone();
+                ctx.after_each(move |_| {
+                    after_each_calls.fetch_add(1, Ordering::SeqCst);
+                });
+                ctx.it("hangs", |_| sleep(10_000));
+            });
+            // act
+            let report = runner.run_all(&[&suite]);
+            // assert
+            assert_eq!(1, report.get_failed());
+            // One environment for the suite's context, one for its example:
+            assert_eq!(2, builds.load(Ordering::SeqCst));
+            assert_eq!(0, after_each_calls.load(Ordering::SeqCst));
+        }
+
+        #[test]
+        fn it_times_out_slow_examples_once_they_returned() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .timeout(Duration::milliseconds(10))
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let value = 42;
+            let borrowed = &value;
+            let suite = suite("suite", borrowed, |ctx| {
+                ctx.it("is slow", |_| sleep(50));
+            });
+            // act
+            let report = runner.run(&suite);
+            // assert
+            assert_eq!(1, report.get_failed());
+            match report.get_context().get_blocks()[0] {
+                BlockReport::Example(_, ref report) => assert_eq!(
+                    &ExampleResult::TimedOut(Duration::milliseconds(10)),
+                    report.get_result()
+                ),
+                _ => panic!("expected an example"),
+            }
+        }
+    }
+
+    mod retries {
+        use super::*;
+
+        use block::suite;
+
+        fn runner(retries: u32) -> Runner {
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .retries(retries)
+                .build()
+                .unwrap();
+            Runner::new(config, vec![])
+        }
+
+        /// An example failing its first `failures` attempts, counting its hooks' calls.
+        fn fixture(failures: usize, hook_calls: Arc<AtomicUsize>) -> Suite<()> {
+            let attempts = Arc::new(AtomicUsize::new(0));
+            suite("suite", (), move |ctx| {
+                let hook_calls = hook_calls.clone();
+                ctx.before_each(move |_| {
+                    hook_calls.fetch_add(1, Ordering::SeqCst);
+                });
+                ctx.it("is flaky", move |_| {
+                    attempts.fetch_add(1, Ordering::SeqCst) >= failures
+                });
+            })
+        }
+
+        #[test]
+        fn it_retries_failing_examples() {
+            // arrange
+            let runner = runner(3);
+            let hook_calls = Arc::new(AtomicUsize::new(0));
+            // act
+            let report = runner.run(&fixture(2, hook_calls.clone()));
+            // assert
+            assert_eq!(3, hook_calls.load(Ordering::SeqCst));
+            assert_eq!(1, report.get_passed());
+            assert_eq!(1, report.get_flaky());
+            assert!(report.is_success());
+            match report.get_context().get_blocks()[0] {
+                BlockReport::Example(_, ref report) => {
+                    let results: Vec<_> = report
+                        .get_attempts()
+                        .iter()
+                        .map(|attempt| attempt.get_result().clone())
+                        .collect();
+                    assert_eq!(
+                        vec![
+                            ExampleResult::from(false),
+                            ExampleResult::from(false),
+                            ExampleResult::Success,
+                        ],
+                        results
+                    );
+                }
+                _ => panic!("expected an example"),
+            }
+        }
+
+        #[test]
+        fn it_gives_up_after_the_last_retry() {
+            // arrange
+            let runner = runner(1);
+            let hook_calls = Arc::new(AtomicUsize::new(0));
+            // act
+            let report = runner.run(&fixture(2, hook_calls.clone()));
+            // assert
+            assert_eq!(2, hook_calls.load(Ordering::SeqCst));
+            assert_eq!(1, report.get_failed());
+            assert_eq!(0, report.get_flaky());
+        }
+
+        #[test]
+        fn it_lets_contexts_override_the_retries() {
+            // arrange
+            let runner = runner(0);
+            let attempts = Arc::new(AtomicUsize::new(0));
+            let suite = suite("suite", (), move |ctx| {
+                ctx.retries(1);
+                ctx.it("is flaky", move |_| {
+                    attempts.fetch_add(1, Ordering::SeqCst) >= 1
+                });
+            });
+            // act
+            let report = runner.run(&suite);
+            // assert
+            assert_eq!(1, report.get_passed());
+            assert_eq!(1, report.get_flaky());
+        }
+    }
+
+    mod mutable_examples {
+        use super::*;
+
+        use block::suite;
+
+        #[test]
+        fn it_gives_each_example_its_own_environment() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .retries(1)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let attempts = Arc::new(AtomicUsize::new(0));
+            let suite = suite("suite", vec![1, 2, 3], move |ctx| {
+                ctx.before_each(|env| env.push(4));
+                ctx.it_mut("pops", |env| env.pop() == Some(4));
+                ctx.it_mut("is flaky", move |env| {
+                    // Retries get a fresh environment as well:
+                    let is_fresh = env.len() == 4;
+                    env.clear();
+                    is_fresh && attempts.fetch_add(1, Ordering::SeqCst) > 0
+                });
+                ctx.it("is untouched", |env| *env == vec![1, 2, 3, 4]);
+            });
+            // act
+            let report = runner.run(&suite);
+            // assert
+            assert_eq!(3, report.get_passed());
+            assert_eq!(1, report.get_flaky());
+        }
+
+        #[test]
+        fn it_gives_timed_examples_mutable_access() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .timeout(Duration::seconds(10))
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let suite = suite("suite", 1, |ctx| {
+                ctx.it_mut("increments", |env| {
+                    *env += 1;
+                    *env == 2
+                });
+            });
+            // act
+            let report = runner.run(&suite);
+            // assert
+            assert_eq!(1, report.get_passed());
+        }
+    }
+
+    mod factories {
+        use super::*;
+
+        use block::suite_with;
+        use std::rc::Rc;
+
+        /// An environment that can't be cloned.
+        #[derive(Debug, Default)]
+        struct Log(Mutex<Vec<&'static str>>);
+
+        impl Log {
+            fn push(&self, entry: &'static str) {
+                self.0.lock().unwrap().push(entry);
+            }
+
+            fn entries(&self) -> Vec<&'static str> {
+                self.0.lock().unwrap().clone()
+            }
+        }
+
+        #[test]
+        fn it_builds_a_fresh_environment_per_block() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(true)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let builds = Arc::new(AtomicUsize::new(0));
+            let counter = builds.clone();
+            let suite = suite_with(
+                "suite",
+                move || {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    Log::default()
+                },
+                |ctx| {
+                    ctx.before_all(|log| log.push("before_all"));
+                    ctx.before_each(|log| log.push("before_each"));
+                    ctx.it("is set up", |log| log.entries() == vec!["before_each"]);
+                    ctx.it_mut("is set up as well", |log| {
+                        log.push("example");
+                        log.entries() == vec!["before_each", "example"]
+                    });
+                    ctx.context("context", |ctx| {
+                        ctx.it("is set up by the enclosing context", |log| {
+                            log.entries() == vec!["before_each"]
+                        });
+                    });
+                },
+            );
+            // act
+            let report = runner.run(&suite);
+            // assert
+            assert_eq!(3, report.get_passed());
+            // The root context, three examples and the nested context:
+            assert_eq!(5, builds.load(Ordering::SeqCst));
+        }
+
+        #[test]
+        fn it_builds_no_environments_for_blocks_that_dont_get_executed() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let builds = Arc::new(AtomicUsize::new(0));
+            let counter = builds.clone();
+            let suite = suite_with(
+                "suite",
+                move || counter.fetch_add(1, Ordering::SeqCst),
+                |ctx| {
+                    ctx.context("failing", |ctx| {
+                        ctx.try_before_all(|_| false);
+                        ctx.it("a", |_| {});
+                        ctx.it("b", |_| {});
+                        ctx.it("c", |_| {});
+                    });
+                    ctx.xcontext("pending", "later", |ctx| {
+                        ctx.it("d", |_| {});
+                        ctx.it("e", |_| {});
+                    });
+                },
+            );
+            // act
+            let report = runner.run(&suite);
+            // assert
+            assert_eq!(3, report.get_failed());
+            assert_eq!(2, report.get_pending());
+            // The root context and the failing context, for its `before_all` hook:
+            assert_eq!(2, builds.load(Ordering::SeqCst));
+        }
+
+        #[test]
+        fn it_calls_the_each_hooks_of_enclosing_contexts_for_nested_blocks() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let torn_down = Arc::new(Mutex::new(vec![]));
+            let (outer, inner) = (torn_down.clone(), torn_down.clone());
+            let suite = suite_with("suite", Log::default, |ctx| {
+                ctx.before_each(|log| log.push("outer"));
+                ctx.after_each(move |log| outer.lock().unwrap().push(log.entries()));
+                ctx.context("context", |ctx| {
+                    ctx.try_before_all(|log| log.entries() == vec!["outer"]);
+                    ctx.before_each(|log| log.push("inner"));
+                    ctx.after_each(move |log| {
+                        log.push("torn down");
+                        inner.lock().unwrap().push(log.entries());
+                    });
+                    ctx.it("is set up from outer to inner", |log| {
+                        log.entries() == vec!["outer", "inner"]
+                    });
+                });
+            });
+            // act
+            let report = runner.run(&suite);
+            // assert
+            assert_eq!(1, report.get_passed());
+            assert!(report.is_success());
+            let example = vec!["outer", "inner", "torn down"];
+            // The example's environment gets torn down from inner to outer,
+            // before the context's own environment gets torn down by the outer hook:
+            assert_eq!(
+                vec![example.clone(), example, vec!["outer"]],
+                *torn_down.lock().unwrap()
+            );
+        }
+
+        #[test]
+        fn it_runs_environments_that_are_not_send_serially() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(true)
+                .timeout(Duration::milliseconds(1))
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let suite = suite_with(
+                "suite",
+                || Rc::new(Cell::new(0)),
+                |ctx| {
+                    ctx.before_each(|counter| counter.set(counter.get() + 1));
+                    ctx.it("is set up", |counter| counter.get() == 1);
+                    ctx.it("is slow", |_| {
+                        ::std::thread::sleep(::std::time::Duration::from_millis(10))
+                    });
+                },
+            );
+            // act
+            let report = runner.run_serial(&suite);
+            // assert
+            assert_eq!(1, report.get_passed());
+            assert_eq!(1, report.get_failed());
+            match report.get_context().get_blocks()[1] {
+                BlockReport::Example(_, ref report) => {
+                    let timeout = Duration::milliseconds(1);
+                    assert_eq!(&ExampleResult::TimedOut(timeout), report.get_result());
+                }
+                _ => panic!("expected an example"),
+            }
+        }
+    }
+
+    mod impl_visitor_block_for_runner {
+        use super::*;
+
+        use block::Environment;
+
         #[test]
         fn it_can_be_called() {
             // arrange
@@ -718,7 +3139,9 @@ mod tests {
             let block = Block::Example(Example::fixture_success());
             // act
             // assert
-            runner.visit(&block, &mut ());
+            let environment = Environment::cloned(());
+            let execution = Execution::serial(&environment);
+            runner.visit(&block, &mut (), &Ancestry::default(), &execution);
         }
     }
 }
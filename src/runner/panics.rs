@@ -0,0 +1,219 @@
+//! Capture of panics' details, so that examples failing by panicking report where they did.
+//!
+//! Panics get caught where examples get called (see `Context::it`), which only gets their payload.
+//! Their location and backtrace are only known to the panic hook though,
+//! which thus records them for the panicking thread to pick them up once the panic got caught.
+
+use std::any::Any;
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::cell::RefCell;
+use std::panic;
+
+use report::ExampleResult;
+
+/// Frames of these modules are implementation details of running examples,
+/// which get trimmed from backtraces.
+const INTERNAL_MODULES: &[&str] = &["std", "core", "alloc", "rspec", "rayon", "rayon_core"];
+
+/// The frame through which libtest calls tests (when run by `cargo test`),
+/// which gets trimmed from backtraces along with all the frames calling it.
+/// Libtest's frames are trimmed this way rather than as those of an internal `test` module,
+/// which would trim the frames of any crate named `test` as well.
+const LIBTEST_ENTRY: &str = "test::__rust_begin_short_backtrace";
+
+/// The details of a panic, as recorded by the panic hook.
+#[derive(Clone, Default, Debug)]
+struct Details {
+    location: Option<String>,
+    backtrace: Option<String>,
+}
+
+thread_local! {
+    static LAST_PANIC: RefCell<Option<Details>> = const { RefCell::new(None) };
+}
+
+/// Installs a panic hook recording panics' details rather than writing them to stderr
+/// (see [`failure`](fn.failure.html)).
+///
+/// Backtraces get captured if enabled by `RUST_BACKTRACE` (or `RUST_LIB_BACKTRACE`).
+pub(crate) fn install_hook() {
+    panic::set_hook(Box::new(|info| {
+        let backtrace = Backtrace::capture();
+        let details = Details {
+            location: info.location().map(ToString::to_string),
+            backtrace: match backtrace.status() {
+                BacktraceStatus::Captured => Some(trim(&backtrace.to_string())),
+                _ => None,
+            },
+        };
+        LAST_PANIC.with(|last_panic| *last_panic.borrow_mut() = Some(details));
+    }));
+}
+
+/// Returns the failure of an example whose panic got caught, given the panic's payload
+/// (see [`message`](fn.message.html)).
+pub(crate) fn failure(payload: Box<dyn Any + Send>) -> ExampleResult {
+    ExampleResult::Failure(message(payload))
+}
+
+/// Returns the message of a panic that got caught, given its payload.
+///
+/// Includes the panic's location and backtrace, if recorded by the hook
+/// (see [`install_hook`](fn.install_hook.html)).
+pub(crate) fn message(payload: Box<dyn Any + Send>) -> Option<String> {
+    let details = LAST_PANIC
+        .with(|last_panic| last_panic.borrow_mut().take())
+        .unwrap_or_default();
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|message| (*message).to_owned())
+        .or_else(|| payload.downcast_ref::<String>().cloned());
+    let mut message = match (message, details.location) {
+        (Some(message), Some(location)) => {
+            format!("thread panicked at '{}', {}", message, location)
+        }
+        (Some(message), None) => format!("thread panicked at '{}'", message),
+        (None, Some(location)) => format!("thread panicked at {}", location),
+        (None, None) => return None,
+    };
+    if let Some(backtrace) = details.backtrace {
+        message.push_str("\nstack backtrace:\n");
+        message.push_str(&backtrace);
+    }
+    Some(message)
+}
+
+/// Removes the frames of the standard library, rspec and rayon from a formatted backtrace,
+/// as well as those of libtest and the C runtime, renumbering the remaining ones.
+fn trim(backtrace: &str) -> String {
+    // Frames start with their number and symbol, followed by their source locations (if any):
+    let mut frames: Vec<(&str, Vec<&str>)> = vec![];
+    for line in backtrace.lines() {
+        match line.trim_start().split_once(": ") {
+            Some((number, symbol)) if number.bytes().all(|b| b.is_ascii_digit()) => {
+                frames.push((symbol, vec![]));
+            }
+            _ => {
+                if let Some((_, ref mut lines)) = frames.last_mut() {
+                    lines.push(line);
+                }
+            }
+        }
+    }
+    let mut trimmed = String::new();
+    let mut number = 0;
+    for (symbol, lines) in frames {
+        if symbol.starts_with(LIBTEST_ENTRY) {
+            break;
+        }
+        if is_internal(symbol) {
+            continue;
+        }
+        trimmed.push_str(&format!("{:4}: {}\n", number, symbol));
+        for line in lines {
+            trimmed.push_str(line);
+            trimmed.push('\n');
+        }
+        number += 1;
+    }
+    trimmed
+}
+
+fn is_internal(symbol: &str) -> bool {
+    // Trait implementations' frames look like `<std::…::Foo as core::…::Bar>::baz`:
+    let path = symbol.trim_start_matches('<');
+    // Calls through function pointers look like `<fn() -> … as core::…::FnOnce<()>>::call_once`:
+    if path.starts_with("fn(") {
+        return true;
+    }
+    match path.find("::") {
+        Some(index) => INTERNAL_MODULES.contains(&&path[..index]),
+        // Frames of the C runtime, e.g. `__libc_start_main`:
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use block::suite;
+    use report::{BlockReport, Report};
+    use runner::{ConfigurationBuilder, Runner};
+
+    #[test]
+    fn it_reports_where_examples_panicked() {
+        // arrange
+        let config = ConfigurationBuilder::default()
+            .parallel(false)
+            .build()
+            .unwrap();
+        let runner = Runner::new(config, vec![]);
+        let mut line = 0;
+        let suite = suite("suite", (), |ctx| {
+            line = line!() + 1;
+            ctx.it("panics", |_| -> () { panic!("boom") });
+        });
+        // act
+        let report = runner.run(&suite);
+        // assert
+        let result = match report.get_context().get_blocks()[0] {
+            BlockReport::Example(_, ref report) => report.get_result().clone(),
+            _ => panic!("expected an example"),
+        };
+        let location = format!("'boom', {}:{}:", file!(), line);
+        match result {
+            ExampleResult::Failure(Some(ref message)) => assert!(message.contains(&location)),
+            _ => panic!("expected a failure"),
+        }
+        assert_eq!(report.get_failed(), 1);
+    }
+
+    #[test]
+    fn it_reports_the_payload_and_location() {
+        // arrange
+        let details = Details {
+            location: Some("src/lib.rs:1:2".to_owned()),
+            backtrace: None,
+        };
+        LAST_PANIC.with(|last_panic| *last_panic.borrow_mut() = Some(details));
+        // act
+        let result = failure(Box::new("boom"));
+        // assert
+        let message = "thread panicked at 'boom', src/lib.rs:1:2".to_owned();
+        assert_eq!(result, ExampleResult::Failure(Some(message)));
+        assert_eq!(
+            failure(Box::new(42)),
+            ExampleResult::Failure(None),
+            "details should only get reported once"
+        );
+    }
+
+    #[test]
+    fn it_trims_backtraces() {
+        let backtrace = "   0: std::backtrace::Backtrace::capture
+             at /rustc/library/std/src/backtrace.rs:296:9
+   1: rspec::runner::panics::install_hook::{{closure}}
+   2: specs::main::{{closure}}
+             at ./tests/specs.rs:12:13
+   3: <alloc::boxed::Box<F,A> as core::ops::function::Fn<Args>>::call
+   4: rayon_core::registry::WorkerThread::execute
+   5: <specs::Subject as core::fmt::Debug>::fmt
+   6: <fn() as core::ops::function::FnOnce<()>>::call_once
+   7: test::helpers::check
+             at ./src/helpers.rs:3:5
+   8: test::__rust_begin_short_backtrace::<core::result::Result<(), alloc::string::String>>
+   9: test::run_test_in_process::{closure#0}
+  10: __libc_start_main
+";
+        assert_eq!(
+            trim(backtrace),
+            "   0: specs::main::{{closure}}
+             at ./tests/specs.rs:12:13
+   1: <specs::Subject as core::fmt::Debug>::fmt
+   2: test::helpers::check
+             at ./src/helpers.rs:3:5
+"
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! Seeded randomization of the order in which a context's blocks get executed.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Name of the environment variable providing the default seed of [`rspec::run`](../fn.run.html)
+/// and of the [`cli`](../cli/index.html) (see [`Configuration.seed`](struct.Configuration.html#structfield.seed)).
+pub const SEED_ENV_VAR: &str = "RSPEC_SEED";
+
+/// Returns the seed provided via the `RSPEC_SEED` environment variable, if any.
+pub(crate) fn seed_from_env() -> Option<u64> {
+    ::std::env::var(SEED_ENV_VAR)
+        .ok()
+        .and_then(|seed| seed.trim().parse().ok())
+}
+
+/// Returns a fresh seed for runs that ask for random ordering without providing one.
+pub(crate) fn random_seed() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    if let Ok(duration) = SystemTime::now().duration_since(UNIX_EPOCH) {
+        hasher.write_u128(duration.as_nanos());
+    }
+    // Keep seeds short enough to be conveniently copied from the logs:
+    hasher.finish() % 100_000
+}
+
+/// A small pseudo-random number generator (SplitMix64).
+///
+/// We purposely don't use a general-purpose generator here, as a given seed
+/// has to reproduce the same order across platforms and releases.
+pub(crate) struct Random {
+    state: u64,
+}
+
+impl Random {
+    /// Creates a generator for the context with the given full name.
+    ///
+    /// Deriving each context's generator from its name (rather than sharing a single one)
+    /// keeps orders reproducible, regardless of the order in which contexts get visited.
+    pub fn new(seed: u64, name: &str) -> Self {
+        // FNV-1a:
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in name.bytes() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        Random { state: seed ^ hash }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Shuffles `items` in place (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = (self.next_u64() % (i as u64 + 1)) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shuffled(seed: u64, name: &str) -> Vec<usize> {
+        let mut items: Vec<usize> = (0..10).collect();
+        Random::new(seed, name).shuffle(&mut items);
+        items
+    }
+
+    #[test]
+    fn it_is_reproducible() {
+        assert_eq!(
+            shuffled(42, "suite > context"),
+            shuffled(42, "suite > context")
+        );
+    }
+
+    #[test]
+    fn it_depends_on_seed_and_name() {
+        assert_ne!(
+            shuffled(42, "suite > context"),
+            shuffled(43, "suite > context")
+        );
+        assert_ne!(
+            shuffled(42, "suite > context"),
+            shuffled(42, "suite > other")
+        );
+    }
+
+    #[test]
+    fn it_permutes() {
+        let mut items = shuffled(42, "suite");
+        items.sort();
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,43 @@
+use header::SuiteHeader;
+use report::SuiteReport;
+use runner::{Execution, Runner};
+
+use block::Suite;
+
+/// A test suite with its environment type erased, allowing suites of different
+/// environment types to be run together (see [`Runner::run_all`](struct.Runner.html#method.run_all)).
+///
+/// Its examples get run on threads of their own, allowing the runner to give up on them
+/// once they timed out (see [`Configuration.timeout`](struct.Configuration.html#structfield.timeout)).
+///
+/// Only suites whose environments are `'static`, `Send` and `Sync` are runnable this way,
+/// and thus with [`cli::run`](../cli/fn.run.html), [`cli::run_all`](../cli/fn.run_all.html)
+/// or [`register!`](../macro.register.html). Suites of other environments have to be run
+/// one by one with [`Runner::run_serial`](struct.Runner.html#method.run_serial) instead.
+pub trait RunnableSuite: Send + Sync {
+    /// Returns the suite's header.
+    fn header(&self) -> &SuiteHeader;
+
+    /// Evaluates the suite's examples with the given runner, returning its report.
+    fn evaluate(&self, runner: &Runner) -> SuiteReport;
+
+    /// Returns the full names of the suite's examples the given runner would execute.
+    fn list(&self, runner: &Runner) -> Vec<String>;
+}
+
+impl<T> RunnableSuite for Suite<T>
+where
+    T: 'static + Send + Sync + ::std::fmt::Debug,
+{
+    fn header(&self) -> &SuiteHeader {
+        &self.header
+    }
+
+    fn evaluate(&self, runner: &Runner) -> SuiteReport {
+        runner.evaluate_suite(self, &Execution::threaded(&self.environment))
+    }
+
+    fn list(&self, runner: &Runner) -> Vec<String> {
+        runner.list(self)
+    }
+}
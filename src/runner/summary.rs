@@ -0,0 +1,168 @@
+use std::process::{ExitCode, Termination};
+
+use time::Duration;
+
+use report::Report;
+
+/// The outcome of all of a Runner's runs, to be returned from a test binary's `main`
+/// (see [`Runner::finish`](struct.Runner.html#method.finish)).
+///
+/// Exits with code `101` upon failure, just like cargo's own test harness.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate rspec;
+/// #
+/// pub fn main() -> rspec::RunSummary {
+///     rspec::run(&rspec::describe("a test suite", (), |ctx| {
+///         ctx.it("passes", |_env| true);
+///     }))
+/// }
+/// ```
+#[must_use = "a test binary should exit with its run's exit code"]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RunSummary {
+    success: bool,
+    failure: bool,
+    passed: u32,
+    failed: u32,
+    ignored: u32,
+    pending: u32,
+    filtered: u32,
+    not_run: u32,
+    flaky: u32,
+    duration: Duration,
+}
+
+impl Default for RunSummary {
+    fn default() -> Self {
+        RunSummary {
+            success: true,
+            failure: false,
+            passed: 0,
+            failed: 0,
+            ignored: 0,
+            pending: 0,
+            filtered: 0,
+            not_run: 0,
+            flaky: 0,
+            duration: Duration::zero(),
+        }
+    }
+}
+
+impl RunSummary {
+    /// Adds a run's report to the summary.
+    pub fn add<R: Report>(&mut self, report: &R) {
+        self.success &= report.is_success();
+        self.failure |= report.is_failure();
+        self.passed += report.get_passed();
+        self.failed += report.get_failed();
+        self.ignored += report.get_ignored();
+        self.pending += report.get_pending();
+        self.filtered += report.get_filtered();
+        self.not_run += report.get_not_run();
+        self.flaky += report.get_flaky();
+        self.duration += report.get_duration();
+    }
+
+    /// Returns `101` upon failure, or `0` otherwise.
+    pub fn exit_code(&self) -> ExitCode {
+        if self.failure {
+            // Cargo test failure returns 101 (see `impl Drop for Runner`).
+            ExitCode::from(101)
+        } else {
+            ExitCode::SUCCESS
+        }
+    }
+}
+
+impl Report for RunSummary {
+    fn is_success(&self) -> bool {
+        self.success
+    }
+
+    fn is_failure(&self) -> bool {
+        self.failure
+    }
+
+    fn get_passed(&self) -> u32 {
+        self.passed
+    }
+
+    fn get_failed(&self) -> u32 {
+        self.failed
+    }
+
+    fn get_ignored(&self) -> u32 {
+        self.ignored
+    }
+
+    fn get_pending(&self) -> u32 {
+        self.pending
+    }
+
+    fn get_filtered(&self) -> u32 {
+        self.filtered
+    }
+
+    fn get_not_run(&self) -> u32 {
+        self.not_run
+    }
+
+    fn get_flaky(&self) -> u32 {
+        self.flaky
+    }
+
+    fn get_duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+impl Termination for RunSummary {
+    fn report(self) -> ExitCode {
+        self.exit_code()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use header::*;
+    use report::*;
+
+    fn report(result: ExampleResult) -> SuiteReport {
+        let context = ContextReport::new(
+            vec![BlockReport::Example(
+                ExampleHeader::default(),
+                ExampleReport::new(result, Duration::milliseconds(10)),
+            )],
+            Duration::milliseconds(10),
+        );
+        SuiteReport::new(SuiteHeader::new(SuiteLabel::Suite, "suite"), context, false)
+    }
+
+    #[test]
+    fn it_succeeds_without_runs() {
+        let summary = RunSummary::default();
+        assert!(summary.is_success());
+        assert_eq!(summary.exit_code(), ExitCode::SUCCESS);
+    }
+
+    #[test]
+    fn it_adds_up_reports() {
+        // arrange
+        let mut summary = RunSummary::default();
+        // act
+        summary.add(&report(ExampleResult::Success));
+        summary.add(&report(ExampleResult::Failure(None)));
+        // assert
+        assert_eq!(summary.get_passed(), 1);
+        assert_eq!(summary.get_failed(), 1);
+        assert_eq!(summary.get_duration(), Duration::milliseconds(20));
+        assert!(summary.is_failure());
+        assert_eq!(summary.exit_code(), ExitCode::from(101));
+    }
+}
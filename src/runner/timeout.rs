@@ -0,0 +1,96 @@
+//! Execution of examples which are only allowed to take a limited amount of time.
+
+use std::convert::TryFrom;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration as StdDuration;
+
+use time::Duration;
+
+use block::ExampleFunction;
+use report::ExampleResult;
+
+/// Executes `function` on a separate thread, giving up on it after `timeout`.
+///
+/// The environment gets moved onto that thread, and back once the example returns (in time).
+/// As there is no way of aborting a thread, an example that timed out keeps running
+/// in the background (with the environment) until it returns or the process exits,
+/// leaving no environment to return.
+pub(crate) fn run_with_timeout<T>(
+    function: &ExampleFunction<T>,
+    mut environment: T,
+    timeout: Duration,
+) -> (ExampleResult, Option<T>)
+where
+    T: Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    let function = function.clone();
+    let spawned = thread::Builder::new().spawn(move || {
+        let result = function(&mut environment);
+        // The receiver is gone if the example timed out, in which case nobody cares about its result:
+        let _ = sender.send((result, environment));
+    });
+    if let Err(error) = spawned {
+        let message = format!("failed to spawn example thread: {}", error);
+        return (ExampleResult::Failure(Some(message)), None);
+    }
+    let std_timeout = StdDuration::try_from(timeout).unwrap_or_default();
+    match receiver.recv_timeout(std_timeout) {
+        Ok((result, environment)) => (result, Some(environment)),
+        Err(_) => (ExampleResult::TimedOut(timeout), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    fn function<F>(function: F) -> ExampleFunction<()>
+    where
+        F: 'static + Send + Sync + Fn(&mut ()) -> ExampleResult,
+    {
+        Arc::new(function)
+    }
+
+    #[test]
+    fn it_returns_the_result_of_fast_examples() {
+        let result = run_with_timeout(
+            &function(|_| ExampleResult::Success),
+            (),
+            Duration::seconds(10),
+        );
+        assert_eq!(result, (ExampleResult::Success, Some(())));
+    }
+
+    #[test]
+    fn it_times_out_slow_examples() {
+        let result = run_with_timeout(
+            &function(|_| {
+                thread::sleep(StdDuration::from_secs(10));
+                ExampleResult::Success
+            }),
+            (),
+            Duration::milliseconds(10),
+        );
+        assert_eq!(
+            result,
+            (ExampleResult::TimedOut(Duration::milliseconds(10)), None)
+        );
+    }
+
+    #[test]
+    fn it_moves_the_environment_back_once_done() {
+        // arrange
+        let function: ExampleFunction<u32> = Arc::new(|environment| {
+            *environment += 1;
+            ExampleResult::Success
+        });
+        // act
+        let (_, environment) = run_with_timeout(&function, 1, Duration::seconds(10));
+        // assert
+        assert_eq!(environment, Some(2));
+    }
+}
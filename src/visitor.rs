@@ -1,6 +1,14 @@
+use runner::{Ancestry, Execution};
+
 pub(crate) trait TestSuiteVisitor<T> {
     type Environment;
     type Output;
 
-    fn visit(&self, visitable: &T, environment: &mut Self::Environment) -> Self::Output;
+    fn visit(
+        &self,
+        visitable: &T,
+        environment: &mut Self::Environment,
+        ancestry: &Ancestry,
+        execution: &Execution<Self::Environment>,
+    ) -> Self::Output;
 }